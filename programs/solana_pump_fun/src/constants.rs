@@ -1,13 +1,32 @@
 pub mod general {
     pub const BPS: u16 = 10_000;
     pub const MAX_ALLOWED_FEE_IN_BPS: u64 = 500;
+    pub const MAX_CREATOR_ALLOCATION_IN_BPS: u64 = 1_000;
+    pub const MAX_LAUNCH_FEE_IN_BPS: u64 = 2_000;
+    pub const MAX_FEE_TIERS: usize = 8;
     pub const DECIMALS: u8 = 9;
     pub const DISCRIMINATOR_SIZE: usize = 8;
+    pub const MAX_PRESET_NAME_LENGTH: usize = 32;
+    pub const PRECISION: u128 = 1_000_000_000_000_000_000;
+    pub const LN_2: u128 = 693_147_180_559_945_309;
+}
+
+pub mod metadata {
+    pub const MAX_NAME_LENGTH: usize = 32;
+    pub const MAX_SYMBOL_LENGTH: usize = 10;
+    pub const MAX_URI_LENGTH: usize = 200;
+    pub const ALLOWED_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
 }
 
 pub mod seeds {
     pub const PLATFORM_SEED: &[u8] = b"platform";
-    pub const MINT_SEED: &[u8] = b"mint";
     pub const TOKEN_SEED: &[u8] = b"token";
     pub const TOKEN_ACCOUNT_SEED: &[u8] = b"token_account";
+    pub const METADATA_SEED: &[u8] = b"metadata";
+    pub const CURVE_PRESET_SEED: &[u8] = b"curve_preset";
+    pub const VESTING_SEED: &[u8] = b"vesting";
+    pub const BUYER_RECORD_SEED: &[u8] = b"buyer_record";
+    pub const TRADER_VOLUME_SEED: &[u8] = b"trader_volume";
+    pub const PLATFORM_STATS_SEED: &[u8] = b"platform_stats";
+    pub const POSITION_SEED: &[u8] = b"position";
 }
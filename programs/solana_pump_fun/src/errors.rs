@@ -12,4 +12,92 @@ pub enum CustomErrors {
     AlreadyLaunched,
     #[msg("Not launched")]
     NotLaunched,
+    #[msg("Real token reserve should not exceed the virtual token reserve or the total supply")]
+    InvalidTokenReserves,
+    #[msg("Not enough tokens left on the bonding curve")]
+    RealTokenReserveExceeded,
+    #[msg("Minimum curve bounds should not exceed the maximum ones")]
+    InvalidCurveBounds,
+    #[msg("Curve parameter is outside the platform bounds")]
+    CurveParamOutOfBounds,
+    #[msg("Preset name should be between 1 and 32 bytes")]
+    InvalidPresetName,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Math underflow")]
+    MathUnderflow,
+    #[msg("Trade amount is below the platform minimum")]
+    TradeTooSmall,
+    #[msg("Trade would yield zero output")]
+    ZeroTradeOutput,
+    #[msg("Custodied sol or tokens no longer cover the curve reserves")]
+    InvariantViolated,
+    #[msg("Token name should not be empty")]
+    EmptyName,
+    #[msg("Token name should not exceed 32 bytes")]
+    NameTooLong,
+    #[msg("Token symbol should not be empty")]
+    EmptySymbol,
+    #[msg("Token symbol should not exceed 10 bytes")]
+    SymbolTooLong,
+    #[msg("Token URI should not be empty")]
+    EmptyUri,
+    #[msg("Token URI should not exceed 200 bytes")]
+    UriTooLong,
+    #[msg("Token metadata should not contain control characters")]
+    ControlCharacters,
+    #[msg("Token URI should start with https://, ipfs:// or ar://")]
+    InvalidUriScheme,
+    #[msg("Mint should have no mint or freeze authority once the supply is minted")]
+    MintNotFixed,
+    #[msg("Metadata update window should not be negative")]
+    InvalidMetadataUpdateWindow,
+    #[msg("Only the token creator can perform this operation")]
+    NotCreator,
+    #[msg("Token metadata can no longer be updated")]
+    MetadataLocked,
+    #[msg("Metaplex metadata account and program are required for this token")]
+    MissingMetadataAccount,
+    #[msg("Trade output is below the minimum accepted amount")]
+    SlippageExceeded,
+    #[msg("Creator token account is required for the initial buy")]
+    MissingCreatorTokenAccount,
+    #[msg("Creator allocation should be positive and not exceed 10% of the total supply")]
+    ExcessiveCreatorAllocation,
+    #[msg("Vesting should last longer than zero and its cliff should be within it")]
+    InvalidVestingSchedule,
+    #[msg("Vesting account is required for the creator allocation")]
+    MissingVestingAccount,
+    #[msg("Nothing has vested since the last claim")]
+    NothingToClaim,
+    #[msg("Launch window should not be negative, should cap wallets and its fee should not exceed 20%")]
+    InvalidLaunchWindow,
+    #[msg("Wallet has reached its buy cap for the launch window")]
+    LaunchWalletCapExceeded,
+    #[msg("Max holding should not exceed 100%")]
+    InvalidMaxHolding,
+    #[msg("Wallet would hold more than the token's holding cap")]
+    MaxHoldingExceeded,
+    #[msg("Price impact fee threshold and slope should not exceed 100%")]
+    InvalidPriceImpactFee,
+    #[msg("Fee tiers should have increasing volumes and discounts of at most 100%")]
+    InvalidFeeTiers,
+    #[msg("Holder discount tiers should have increasing balances and discounts of at most 100%")]
+    InvalidHolderDiscountTiers,
+    #[msg("Token account does not hold the platform token")]
+    InvalidPlatformTokenAccount,
+    #[msg("Curve parameter overrides are disabled by the platform")]
+    CurveOverrideDisabled,
+    #[msg("Curve parameter should not be zero")]
+    ZeroCurveParam,
+    #[msg("Buyer record and trader volume accounts are required for the initial buy")]
+    MissingInitialBuyAccounts,
+    #[msg("Only tokens bought from the curve can be sold back into it")]
+    SellExceedsCurve,
+    #[msg("Token holding cap can only tighten the platform's cap")]
+    MaxHoldingLoosened,
+    #[msg("Token metadata can still be updated by its creator")]
+    MetadataStillUpdatable,
+    #[msg("Division by zero")]
+    DivisionByZero,
 }
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    CurveBounds, CurveType, FeeTier, HolderDiscountTier, LaunchWindow, MetadataPolicy,
+    PriceImpactFee,
+};
+
 #[event]
 pub struct PlatformInitialized {
     pub platform: Pubkey,
@@ -12,8 +17,13 @@ pub struct OwnerChanged {
 }
 
 #[event]
-pub struct FeesChanged {
-    pub new_fees: u64,
+pub struct BuyFeeChanged {
+    pub new_buy_fee_bps: u64,
+}
+
+#[event]
+pub struct SellFeeChanged {
+    pub new_sell_fee_bps: u64,
 }
 
 #[event]
@@ -26,11 +36,82 @@ pub struct VirtualSolChanged {
     pub new_virtual_sol_amount: u64,
 }
 
+#[event]
+pub struct TokenReservesChanged {
+    pub new_virtual_token_reserve: u64,
+    pub new_real_token_reserve: u64,
+}
+
 #[event]
 pub struct TargetPoolBalanceChanged {
     pub new_target_pool_balance: u64,
 }
 
+#[event]
+pub struct CurveTypeChanged {
+    pub new_curve_type: CurveType,
+}
+
+#[event]
+pub struct CurveBoundsChanged {
+    pub new_curve_bounds: CurveBounds,
+}
+
+#[event]
+pub struct MinTradeAmountsChanged {
+    pub new_min_buy_sol_amount: u64,
+    pub new_min_sell_token_amount: u64,
+}
+
+#[event]
+pub struct MetadataPolicyChanged {
+    pub new_metadata_policy: MetadataPolicy,
+    pub new_metadata_update_window: i64,
+}
+
+#[event]
+pub struct LaunchWindowChanged {
+    pub new_launch_window: LaunchWindow,
+}
+
+#[event]
+pub struct MaxHoldingChanged {
+    pub new_max_holding_bps: u64,
+}
+
+#[event]
+pub struct PriceImpactFeeChanged {
+    pub new_price_impact_fee: PriceImpactFee,
+}
+
+#[event]
+pub struct FeeTiersChanged {
+    pub new_fee_tiers: Vec<FeeTier>,
+}
+
+#[event]
+pub struct PlatformTokenChanged {
+    pub new_platform_token_mint: Option<Pubkey>,
+    pub new_holder_discount_tiers: Vec<HolderDiscountTier>,
+}
+
+#[event]
+pub struct CurvePresetCreated {
+    pub curve_preset: Pubkey,
+    pub name: String,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub token: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct CurvePresetClosed {
+    pub curve_preset: Pubkey,
+}
+
 #[event]
 pub struct FeesWithdrawn {
     pub amount: u64,
@@ -39,6 +120,47 @@ pub struct FeesWithdrawn {
 #[event]
 pub struct TokenCreated {
     pub token: Pubkey,
+    pub total_supply: u64,
+}
+
+#[event]
+pub struct VestingCreated {
+    pub token: Pubkey,
+    pub creator: Pubkey,
+    pub total_amount: u64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub token: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMetadataUpdated {
+    pub token: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct TokenMetadataLocked {
+    pub token: Pubkey,
+}
+
+#[event]
+pub struct TokensBought {
+    pub token: Pubkey,
+    pub by: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub fee_amount: u64,
+    pub fee_in_bps: u64,
+    pub price_impact_bps: u64,
 }
 
 #[event]
@@ -46,6 +168,10 @@ pub struct TokensSold {
     pub token: Pubkey,
     pub by: Pubkey,
     pub amount: u64,
+    pub sol_amount: u64,
+    pub fee_amount: u64,
+    pub fee_in_bps: u64,
+    pub price_impact_bps: u64,
 }
 
 #[event]
@@ -3,10 +3,25 @@ use anchor_lang::system_program;
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
-        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
-        Metadata as Metaplex,
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+        update_metadata_accounts_v2, CreateMetadataAccountsV3, Metadata as Metaplex,
+        UpdateMetadataAccountsV2,
+    },
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{
+        mint_to, set_authority,
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_2022::{
+            self,
+            extension::{BaseStateWithExtensions, StateWithExtensions},
+            instruction::AuthorityType,
+        },
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_initialize, token_metadata_update_authority, token_metadata_update_field,
+        Mint, MintTo, SetAuthority, TokenAccount, TokenInterface, TokenMetadataInitialize,
+        TokenMetadataUpdateAuthority, TokenMetadataUpdateField, TransferChecked,
     },
-    token::{self, mint_to, Mint, MintTo, Token, TokenAccount, Transfer as SplTransfer},
 };
 
 mod constants;
@@ -22,17 +37,66 @@ pub mod solana_pump_fun {
 
     pub fn initialize(ctx: Context<Initialize>, init_params: PlatformInitParams) -> Result<()> {
         require!(
-            init_params.fee_in_bps <= constants::general::MAX_ALLOWED_FEE_IN_BPS,
+            init_params.buy_fee_bps <= constants::general::MAX_ALLOWED_FEE_IN_BPS
+                && init_params.sell_fee_bps <= constants::general::MAX_ALLOWED_FEE_IN_BPS,
             errors::CustomErrors::ExcessiveFees
         );
+        require!(
+            init_params.real_token_reserve <= init_params.virtual_token_reserve
+                && init_params.real_token_reserve <= init_params.total_supply,
+            errors::CustomErrors::InvalidTokenReserves
+        );
+        require!(
+            init_params.curve_bounds.is_valid(),
+            errors::CustomErrors::InvalidCurveBounds
+        );
+        require!(
+            init_params.metadata_update_window >= 0,
+            errors::CustomErrors::InvalidMetadataUpdateWindow
+        );
+        require!(
+            init_params.launch_window.is_valid(),
+            errors::CustomErrors::InvalidLaunchWindow
+        );
+        require!(
+            init_params.max_holding_bps <= constants::general::BPS as u64,
+            errors::CustomErrors::InvalidMaxHolding
+        );
+        require!(
+            init_params.price_impact_fee.is_valid(),
+            errors::CustomErrors::InvalidPriceImpactFee
+        );
+        require!(
+            FeeTier::are_valid(&init_params.fee_tiers),
+            errors::CustomErrors::InvalidFeeTiers
+        );
+        require!(
+            HolderDiscountTier::are_valid(&init_params.holder_discount_tiers),
+            errors::CustomErrors::InvalidHolderDiscountTiers
+        );
 
         let platform = &mut ctx.accounts.platform;
 
-        platform.fee_in_bps = init_params.fee_in_bps;
+        platform.buy_fee_bps = init_params.buy_fee_bps;
+        platform.sell_fee_bps = init_params.sell_fee_bps;
         platform.owner = init_params.owner;
         platform.total_supply = init_params.total_supply;
         platform.virtual_sol = init_params.virtual_sol;
+        platform.virtual_token_reserve = init_params.virtual_token_reserve;
+        platform.real_token_reserve = init_params.real_token_reserve;
         platform.target_pool_balance = init_params.target_pool_balance;
+        platform.curve_type = init_params.curve_type;
+        platform.curve_bounds = init_params.curve_bounds;
+        platform.min_buy_sol_amount = init_params.min_buy_sol_amount;
+        platform.min_sell_token_amount = init_params.min_sell_token_amount;
+        platform.metadata_policy = init_params.metadata_policy;
+        platform.metadata_update_window = init_params.metadata_update_window;
+        platform.launch_window = init_params.launch_window;
+        platform.max_holding_bps = init_params.max_holding_bps;
+        platform.price_impact_fee = init_params.price_impact_fee;
+        platform.fee_tiers = init_params.fee_tiers;
+        platform.platform_token_mint = init_params.platform_token_mint;
+        platform.holder_discount_tiers = init_params.holder_discount_tiers;
 
         let platform_initialized_event = events::PlatformInitialized {
             platform: ctx.accounts.platform.key(),
@@ -52,11 +116,30 @@ pub mod solana_pump_fun {
         Ok(())
     }
 
-    pub fn change_fees(ctx: Context<PlatformOperation>, new_fees: u64) -> Result<()> {
-        ctx.accounts.platform.fee_in_bps = new_fees;
+    pub fn change_buy_fee(ctx: Context<PlatformOperation>, new_buy_fee_bps: u64) -> Result<()> {
+        require!(
+            new_buy_fee_bps <= constants::general::MAX_ALLOWED_FEE_IN_BPS,
+            errors::CustomErrors::ExcessiveFees
+        );
+
+        ctx.accounts.platform.buy_fee_bps = new_buy_fee_bps;
+
+        let buy_fee_changed_event = events::BuyFeeChanged { new_buy_fee_bps };
+        emit!(buy_fee_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_sell_fee(ctx: Context<PlatformOperation>, new_sell_fee_bps: u64) -> Result<()> {
+        require!(
+            new_sell_fee_bps <= constants::general::MAX_ALLOWED_FEE_IN_BPS,
+            errors::CustomErrors::ExcessiveFees
+        );
+
+        ctx.accounts.platform.sell_fee_bps = new_sell_fee_bps;
 
-        let fees_changed_event = events::FeesChanged { new_fees };
-        emit!(fees_changed_event);
+        let sell_fee_changed_event = events::SellFeeChanged { new_sell_fee_bps };
+        emit!(sell_fee_changed_event);
 
         Ok(())
     }
@@ -65,6 +148,11 @@ pub mod solana_pump_fun {
         ctx: Context<PlatformOperation>,
         new_total_supply: u64,
     ) -> Result<()> {
+        require!(
+            ctx.accounts.platform.real_token_reserve <= new_total_supply,
+            errors::CustomErrors::InvalidTokenReserves
+        );
+
         ctx.accounts.platform.total_supply = new_total_supply;
 
         let total_supply_changed_event = events::TotalSupplyChanged { new_total_supply };
@@ -87,6 +175,29 @@ pub mod solana_pump_fun {
         Ok(())
     }
 
+    pub fn change_token_reserves(
+        ctx: Context<PlatformOperation>,
+        new_virtual_token_reserve: u64,
+        new_real_token_reserve: u64,
+    ) -> Result<()> {
+        require!(
+            new_real_token_reserve <= new_virtual_token_reserve
+                && new_real_token_reserve <= ctx.accounts.platform.total_supply,
+            errors::CustomErrors::InvalidTokenReserves
+        );
+
+        ctx.accounts.platform.virtual_token_reserve = new_virtual_token_reserve;
+        ctx.accounts.platform.real_token_reserve = new_real_token_reserve;
+
+        let token_reserves_changed_event = events::TokenReservesChanged {
+            new_virtual_token_reserve,
+            new_real_token_reserve,
+        };
+        emit!(token_reserves_changed_event);
+
+        Ok(())
+    }
+
     pub fn change_target_pool_balance(
         ctx: Context<PlatformOperation>,
         new_target_pool_balance: u64,
@@ -101,6 +212,217 @@ pub mod solana_pump_fun {
         Ok(())
     }
 
+    pub fn change_curve_type(
+        ctx: Context<PlatformOperation>,
+        new_curve_type: CurveType,
+    ) -> Result<()> {
+        ctx.accounts.platform.curve_type = new_curve_type;
+
+        let curve_type_changed_event = events::CurveTypeChanged { new_curve_type };
+        emit!(curve_type_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_curve_bounds(
+        ctx: Context<PlatformOperation>,
+        new_curve_bounds: CurveBounds,
+    ) -> Result<()> {
+        require!(
+            new_curve_bounds.is_valid(),
+            errors::CustomErrors::InvalidCurveBounds
+        );
+
+        ctx.accounts.platform.curve_bounds = new_curve_bounds.clone();
+
+        let curve_bounds_changed_event = events::CurveBoundsChanged { new_curve_bounds };
+        emit!(curve_bounds_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_min_trade_amounts(
+        ctx: Context<PlatformOperation>,
+        new_min_buy_sol_amount: u64,
+        new_min_sell_token_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.platform.min_buy_sol_amount = new_min_buy_sol_amount;
+        ctx.accounts.platform.min_sell_token_amount = new_min_sell_token_amount;
+
+        let min_trade_amounts_changed_event = events::MinTradeAmountsChanged {
+            new_min_buy_sol_amount,
+            new_min_sell_token_amount,
+        };
+        emit!(min_trade_amounts_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_metadata_policy(
+        ctx: Context<PlatformOperation>,
+        new_metadata_policy: MetadataPolicy,
+        new_metadata_update_window: i64,
+    ) -> Result<()> {
+        require!(
+            new_metadata_update_window >= 0,
+            errors::CustomErrors::InvalidMetadataUpdateWindow
+        );
+
+        ctx.accounts.platform.metadata_policy = new_metadata_policy;
+        ctx.accounts.platform.metadata_update_window = new_metadata_update_window;
+
+        let metadata_policy_changed_event = events::MetadataPolicyChanged {
+            new_metadata_policy,
+            new_metadata_update_window,
+        };
+        emit!(metadata_policy_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_launch_window(
+        ctx: Context<PlatformOperation>,
+        new_launch_window: LaunchWindow,
+    ) -> Result<()> {
+        require!(
+            new_launch_window.is_valid(),
+            errors::CustomErrors::InvalidLaunchWindow
+        );
+
+        ctx.accounts.platform.launch_window = new_launch_window.clone();
+
+        let launch_window_changed_event = events::LaunchWindowChanged { new_launch_window };
+        emit!(launch_window_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_max_holding(
+        ctx: Context<PlatformOperation>,
+        new_max_holding_bps: u64,
+    ) -> Result<()> {
+        require!(
+            new_max_holding_bps <= constants::general::BPS as u64,
+            errors::CustomErrors::InvalidMaxHolding
+        );
+
+        ctx.accounts.platform.max_holding_bps = new_max_holding_bps;
+
+        let max_holding_changed_event = events::MaxHoldingChanged {
+            new_max_holding_bps,
+        };
+        emit!(max_holding_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_price_impact_fee(
+        ctx: Context<PlatformOperation>,
+        new_price_impact_fee: PriceImpactFee,
+    ) -> Result<()> {
+        require!(
+            new_price_impact_fee.is_valid(),
+            errors::CustomErrors::InvalidPriceImpactFee
+        );
+
+        ctx.accounts.platform.price_impact_fee = new_price_impact_fee.clone();
+
+        let price_impact_fee_changed_event = events::PriceImpactFeeChanged {
+            new_price_impact_fee,
+        };
+        emit!(price_impact_fee_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_fee_tiers(
+        ctx: Context<PlatformOperation>,
+        new_fee_tiers: Vec<FeeTier>,
+    ) -> Result<()> {
+        require!(
+            FeeTier::are_valid(&new_fee_tiers),
+            errors::CustomErrors::InvalidFeeTiers
+        );
+
+        ctx.accounts.platform.fee_tiers = new_fee_tiers.clone();
+
+        let fee_tiers_changed_event = events::FeeTiersChanged { new_fee_tiers };
+        emit!(fee_tiers_changed_event);
+
+        Ok(())
+    }
+
+    pub fn change_platform_token(
+        ctx: Context<PlatformOperation>,
+        new_platform_token_mint: Option<Pubkey>,
+        new_holder_discount_tiers: Vec<HolderDiscountTier>,
+    ) -> Result<()> {
+        require!(
+            HolderDiscountTier::are_valid(&new_holder_discount_tiers),
+            errors::CustomErrors::InvalidHolderDiscountTiers
+        );
+
+        let platform = &mut ctx.accounts.platform;
+        platform.platform_token_mint = new_platform_token_mint;
+        platform.holder_discount_tiers = new_holder_discount_tiers.clone();
+
+        let platform_token_changed_event = events::PlatformTokenChanged {
+            new_platform_token_mint,
+            new_holder_discount_tiers,
+        };
+        emit!(platform_token_changed_event);
+
+        Ok(())
+    }
+
+    pub fn create_curve_preset(
+        ctx: Context<CreateCurvePreset>,
+        curve_preset_params: CurvePresetParams,
+    ) -> Result<()> {
+        require!(
+            !curve_preset_params.name.is_empty()
+                && curve_preset_params.name.len() <= constants::general::MAX_PRESET_NAME_LENGTH,
+            errors::CustomErrors::InvalidPresetName
+        );
+        let config = &curve_preset_params.config;
+        require!(
+            config.total_supply > 0
+                && config.virtual_sol > 0
+                && config.virtual_token_reserve > 0
+                && config.real_token_reserve > 0,
+            errors::CustomErrors::ZeroCurveParam
+        );
+        require!(
+            config.target_pool_balance > config.virtual_sol,
+            errors::CustomErrors::CurveParamOutOfBounds
+        );
+        require!(
+            config.has_valid_reserves(),
+            errors::CustomErrors::InvalidTokenReserves
+        );
+
+        let curve_preset = &mut ctx.accounts.curve_preset;
+        curve_preset.name = curve_preset_params.name.clone();
+        curve_preset.config = curve_preset_params.config;
+
+        let curve_preset_created_event = events::CurvePresetCreated {
+            curve_preset: ctx.accounts.curve_preset.key(),
+            name: curve_preset_params.name,
+        };
+        emit!(curve_preset_created_event);
+
+        Ok(())
+    }
+
+    pub fn close_curve_preset(ctx: Context<CloseCurvePreset>) -> Result<()> {
+        let curve_preset_closed_event = events::CurvePresetClosed {
+            curve_preset: ctx.accounts.curve_preset.key(),
+        };
+        emit!(curve_preset_closed_event);
+
+        Ok(())
+    }
+
     pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
         let seeds = &[constants::seeds::PLATFORM_SEED, &[ctx.bumps.platform]];
         let signer = [&seeds[..]];
@@ -130,23 +452,26 @@ pub mod solana_pump_fun {
         Ok(())
     }
 
-    pub fn create_token(
-        ctx: Context<CreateToken>,
+    pub fn create_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateToken<'info>>,
         create_token_params: CreateTokenParams,
     ) -> Result<()> {
-        let seeds = &[
-            constants::seeds::MINT_SEED,
-            create_token_params.name.as_bytes(),
-            &[ctx.bumps.mint],
-        ];
+        utils::validate_metadata(
+            &create_token_params.name,
+            &create_token_params.symbol,
+            &create_token_params.uri,
+        )?;
+
+        // The platform is the mint and metadata authority of every token it launches
+        let seeds = &[constants::seeds::PLATFORM_SEED, &[ctx.bumps.platform]];
         let signer = [&seeds[..]];
 
         // First, create the token metadata
         // The token was already created by Anchor in the background
         let token_data: DataV2 = DataV2 {
             name: create_token_params.name.clone(),
-            symbol: create_token_params.symbol,
-            uri: create_token_params.uri,
+            symbol: create_token_params.symbol.clone(),
+            uri: create_token_params.uri.clone(),
             seller_fee_basis_points: 0,
             creators: None,
             collection: None,
@@ -156,112 +481,266 @@ pub mod solana_pump_fun {
             ctx.accounts.token_metadata_program.to_account_info(),
             CreateMetadataAccountsV3 {
                 payer: ctx.accounts.signer.to_account_info(),
-                update_authority: ctx.accounts.mint.to_account_info(),
+                update_authority: ctx.accounts.platform.to_account_info(),
                 mint: ctx.accounts.mint.to_account_info(),
                 metadata: ctx.accounts.metadata.to_account_info(),
-                mint_authority: ctx.accounts.mint.to_account_info(),
+                mint_authority: ctx.accounts.platform.to_account_info(),
                 system_program: ctx.accounts.system_program.to_account_info(),
                 rent: ctx.accounts.rent.to_account_info(),
             },
             &signer,
         );
-        create_metadata_accounts_v3(metadata_ctx, token_data, false, true, None)?;
-
-        // Second, initialize the token campaign params
-        let token_info = &mut ctx.accounts.token_info;
-        token_info.token = ctx.accounts.mint.key();
-        token_info.total_supply = ctx.accounts.platform.total_supply;
-        token_info.virtual_sol = ctx.accounts.platform.virtual_sol;
-        token_info.sol_reserve = token_info.virtual_sol;
-        token_info.token_reserve = token_info.total_supply;
-        token_info.target_pool_balance = ctx.accounts.platform.target_pool_balance;
+        let is_mutable = ctx.accounts.platform.metadata_policy == MetadataPolicy::CreatorUpdatable;
+        create_metadata_accounts_v3(metadata_ctx, token_data, false, is_mutable, None)?;
+
+        finish_token_creation(
+            ctx.accounts.token_creation_accounts(),
+            ctx.bumps.platform,
+            ctx.bumps.token_account,
+            &create_token_params,
+            ctx.remaining_accounts,
+        )
+    }
 
-        // Lastly, mint the total supply of the tokens to the token vault associated with this token launch
-        mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    authority: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.token_account.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
-                },
-                &signer,
-            ),
-            ctx.accounts.token_info.total_supply,
+    /// Creates a Token-2022 token with its metadata stored on the mint.
+    pub fn create_token_2022<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateToken2022<'info>>,
+        create_token_params: CreateTokenParams,
+    ) -> Result<()> {
+        utils::validate_metadata(
+            &create_token_params.name,
+            &create_token_params.symbol,
+            &create_token_params.uri,
         )?;
 
-        let token_created_event = events::TokenCreated {
-            token: ctx.accounts.mint.key(),
-        };
-        emit!(token_created_event);
+        let accounts = ctx.accounts.token_creation_accounts();
+        initialize_mint_metadata(&accounts, ctx.bumps.platform, &create_token_params)?;
 
-        Ok(())
+        finish_token_creation(
+            accounts,
+            ctx.bumps.platform,
+            ctx.bumps.token_account,
+            &create_token_params,
+            ctx.remaining_accounts,
+        )
     }
 
-    pub fn init_token_account_for_token(_: Context<InitAccountsForToken>) -> Result<()> {
-        msg!("Token account created");
+    /// Creates a Token-2022 token whose transfers invoke the given transfer hook program. The hook's
+    /// extra accounts are passed to the trade instructions as remaining accounts.
+    pub fn create_token_2022_with_hook<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateToken2022WithHook<'info>>,
+        create_token_params: CreateTokenParams,
+    ) -> Result<()> {
+        utils::validate_metadata(
+            &create_token_params.name,
+            &create_token_params.symbol,
+            &create_token_params.uri,
+        )?;
 
-        Ok(())
+        let accounts = ctx.accounts.token_creation_accounts();
+        initialize_mint_metadata(&accounts, ctx.bumps.platform, &create_token_params)?;
+
+        finish_token_creation(
+            accounts,
+            ctx.bumps.platform,
+            ctx.bumps.token_account,
+            &create_token_params,
+            ctx.remaining_accounts,
+        )
     }
 
-    pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64) -> Result<()> {
+    /// Lets the creator of a token change its metadata until the update window set by the platform
+    /// policy closes or the token graduates, whichever comes first.
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        update_token_metadata_params: UpdateTokenMetadataParams,
+    ) -> Result<()> {
+        utils::validate_metadata(
+            &update_token_metadata_params.name,
+            &update_token_metadata_params.symbol,
+            &update_token_metadata_params.uri,
+        )?;
         require!(
-            !ctx.accounts.token_info.launched,
-            errors::CustomErrors::AlreadyLaunched
+            !ctx.accounts.token_info.launched
+                && Clock::get()?.unix_timestamp <= ctx.accounts.token_info.metadata_update_deadline,
+            errors::CustomErrors::MetadataLocked
         );
 
-        // sol_amount = 101
-        // buy_fee = (101 * 100) / (10000 + 100) = 1
-        // sol_amount_after_fee = 100
-        let fee_amount = utils::calculate_buy_fee(
-            &(sol_amount as u128),
-            &(ctx.accounts.platform.fee_in_bps as u128),
-        );
-        let sol_amount_after_fee = sol_amount - fee_amount;
-        let token_amount = utils::get_amount_out(
-            &(sol_amount_after_fee as u128),
-            &(ctx.accounts.token_info.sol_reserve as u128),
-            &(ctx.accounts.token_info.token_reserve as u128),
-        );
+        let seeds = &[constants::seeds::PLATFORM_SEED, &[ctx.bumps.platform]];
+        let signer = [&seeds[..]];
+
+        if ctx.accounts.token_program.key() == Token2022::id() {
+            // Token-2022 tokens keep their metadata on the mint, which is topped up beforehand in
+            // case the new values need more space
+            let mint_account = ctx.accounts.mint.to_account_info();
+            let mut token_metadata = {
+                let mint_data = mint_account.try_borrow_data()?;
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?
+                    .get_variable_len_extension::<TokenMetadata>()?
+            };
+            let current_metadata_space = token_metadata.tlv_size_of()?;
+            token_metadata.update(Field::Name, update_token_metadata_params.name.clone());
+            token_metadata.update(Field::Symbol, update_token_metadata_params.symbol.clone());
+            token_metadata.update(Field::Uri, update_token_metadata_params.uri.clone());
+            let mint_space = mint_account
+                .data_len()
+                .checked_sub(current_metadata_space)
+                .and_then(|space| space.checked_add(token_metadata.tlv_size_of().ok()?))
+                .ok_or(errors::CustomErrors::MathOverflow)?;
+            utils::top_up_rent(
+                &ctx.accounts.signer.to_account_info(),
+                &mint_account,
+                &ctx.accounts.system_program.to_account_info(),
+                mint_space,
+            )?;
+
+            for (field, value) in [
+                (Field::Name, update_token_metadata_params.name.clone()),
+                (Field::Symbol, update_token_metadata_params.symbol.clone()),
+                (Field::Uri, update_token_metadata_params.uri.clone()),
+            ] {
+                token_metadata_update_field(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TokenMetadataUpdateField {
+                            token_program_id: ctx.accounts.token_program.to_account_info(),
+                            metadata: mint_account.clone(),
+                            update_authority: ctx.accounts.platform.to_account_info(),
+                        },
+                        &signer,
+                    ),
+                    field,
+                    value,
+                )?;
+            }
+        } else {
+            // Tokens created through `create_token` keep their metadata in a Metaplex account
+            let (Some(metadata), Some(token_metadata_program)) =
+                (&ctx.accounts.metadata, &ctx.accounts.token_metadata_program)
+            else {
+                return err!(errors::CustomErrors::MissingMetadataAccount);
+            };
+            let token_data: DataV2 = DataV2 {
+                name: update_token_metadata_params.name.clone(),
+                symbol: update_token_metadata_params.symbol.clone(),
+                uri: update_token_metadata_params.uri.clone(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            };
+            update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    token_metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: metadata.to_account_info(),
+                        update_authority: ctx.accounts.platform.to_account_info(),
+                    },
+                    &signer,
+                ),
+                None,
+                Some(token_data),
+                None,
+                None,
+            )?;
+        }
 
-        ctx.accounts.platform.accumulated_fees += fee_amount;
+        let token_metadata_updated_event = events::TokenMetadataUpdated {
+            token: ctx.accounts.mint.key(),
+            name: update_token_metadata_params.name,
+            symbol: update_token_metadata_params.symbol,
+            uri: update_token_metadata_params.uri,
+        };
+        emit!(token_metadata_updated_event);
 
-        ctx.accounts.token_info.sol_reserve += sol_amount_after_fee;
-        ctx.accounts.token_info.token_reserve -= token_amount;
+        Ok(())
+    }
 
+    /// Makes the metadata of a creator-updatable token immutable once its update window has
+    /// closed or it has graduated. Anyone can call this.
+    pub fn lock_token_metadata(ctx: Context<LockTokenMetadata>) -> Result<()> {
+        let token_info = &ctx.accounts.token_info;
         require!(
-            ctx.accounts.token_info.sol_reserve <= ctx.accounts.token_info.target_pool_balance,
-            errors::CustomErrors::BondingCurveBreached
+            token_info.metadata_update_deadline != 0,
+            errors::CustomErrors::MetadataLocked
+        );
+        require!(
+            token_info.launched
+                || Clock::get()?.unix_timestamp > token_info.metadata_update_deadline,
+            errors::CustomErrors::MetadataStillUpdatable
         );
 
-        // Check the current market cap and launch the token if it's been hit
-        if ctx.accounts.token_info.sol_reserve == ctx.accounts.token_info.target_pool_balance {
-            ctx.accounts.token_info.launched = true;
+        let seeds = &[constants::seeds::PLATFORM_SEED, &[ctx.bumps.platform]];
+        let signer = [&seeds[..]];
+
+        if ctx.accounts.token_program.key() == Token2022::id() {
+            revoke_mint_metadata_authority(
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.mint.to_account_info(),
+                &ctx.accounts.platform.to_account_info(),
+                &signer,
+            )?;
+        } else {
+            let (Some(metadata), Some(token_metadata_program)) =
+                (&ctx.accounts.metadata, &ctx.accounts.token_metadata_program)
+            else {
+                return err!(errors::CustomErrors::MissingMetadataAccount);
+            };
+            update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    token_metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: metadata.to_account_info(),
+                        update_authority: ctx.accounts.platform.to_account_info(),
+                    },
+                    &signer,
+                ),
+                None,
+                None,
+                None,
+                Some(false),
+            )?;
         }
 
-        // Transfer sol amount (after applying fee) from signer to token info account
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.signer.to_account_info(),
-                    to: ctx.accounts.token_info.to_account_info(),
-                },
-            ),
-            sol_amount_after_fee,
-        )?;
+        // A zero deadline marks the metadata as locked for good
+        ctx.accounts.token_info.metadata_update_deadline = 0;
 
-        // Transfer fees (in sol) to the platform account
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.signer.to_account_info(),
-                    to: ctx.accounts.platform.to_account_info(),
-                },
-            ),
-            fee_amount,
-        )?;
+        let token_metadata_locked_event = events::TokenMetadataLocked {
+            token: ctx.accounts.mint.key(),
+        };
+        emit!(token_metadata_locked_event);
+
+        Ok(())
+    }
+
+    pub fn claim_vested<'info>(ctx: Context<'_, '_, '_, 'info, ClaimVested<'info>>) -> Result<()> {
+        // Vested tokens only unlock once the token has left the curve, so they can never be sold
+        // into it against the sol buyers paid in
+        require!(
+            ctx.accounts.token_info.launched,
+            errors::CustomErrors::NotLaunched
+        );
+
+        let claimable_amount = ctx
+            .accounts
+            .vesting
+            .vested_amount(Clock::get()?.unix_timestamp)?
+            .checked_sub(ctx.accounts.vesting.claimed_amount)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        require!(claimable_amount > 0, errors::CustomErrors::NothingToClaim);
+
+        ctx.accounts.vesting.claimed_amount = ctx
+            .accounts
+            .vesting
+            .claimed_amount
+            .checked_add(claimable_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        ctx.accounts.token_info.vesting_token_reserve = ctx
+            .accounts
+            .token_info
+            .vesting_token_reserve
+            .checked_sub(claimable_amount)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
 
         let mint_token_account_key = ctx.accounts.mint.key();
         let seeds = &[
@@ -271,156 +750,269 @@ pub mod solana_pump_fun {
         ];
         let signer = [&seeds[..]];
 
-        // Transfer tokens to user
-        token::transfer(
+        // Transfer the vested tokens to the creator
+        utils::transfer_checked_with_hook(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                SplTransfer {
-                    from: ctx.accounts.source_token_account.to_account_info().clone(),
-                    to: ctx.accounts.user_token_account.to_account_info().clone(),
-                    authority: ctx.accounts.source_token_account.to_account_info().clone(),
+                TransferChecked {
+                    from: ctx.accounts.source_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: ctx.accounts.source_token_account.to_account_info(),
                 },
                 &signer,
-            ),
-            token_amount,
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            claimable_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        ctx.accounts.source_token_account.reload()?;
+        ctx.accounts.token_info.check_reserve_invariants(
+            &ctx.accounts.token_info.to_account_info(),
+            &ctx.accounts.source_token_account,
         )?;
 
+        let vested_tokens_claimed_event = events::VestedTokensClaimed {
+            token: ctx.accounts.mint.key(),
+            creator: ctx.accounts.signer.key(),
+            amount: claimable_amount,
+        };
+        emit!(vested_tokens_claimed_event);
+
         Ok(())
     }
 
-    pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64, name: String) -> Result<()> {
-        require!(
-            !ctx.accounts.token_info.launched,
-            errors::CustomErrors::AlreadyLaunched
-        );
-
-        let sol_amount = utils::get_amount_out(
-            &(token_amount as u128),
-            &(ctx.accounts.token_info.token_reserve as u128),
-            &(ctx.accounts.token_info.sol_reserve as u128),
-        );
-        let fee_amount = utils::calculate_sell_fee(
-            &(sol_amount as u128),
-            &(ctx.accounts.platform.fee_in_bps as u128),
-        );
-        let sol_amount_after_fee = sol_amount - fee_amount;
+    pub fn init_token_account_for_token(_: Context<InitAccountsForToken>) -> Result<()> {
+        msg!("Token account created");
 
-        ctx.accounts.token_info.sol_reserve -= sol_amount;
-        ctx.accounts.token_info.token_reserve += token_amount;
+        Ok(())
+    }
 
-        ctx.accounts.platform.accumulated_fees += fee_amount;
+    pub fn buy_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyTokens<'info>>,
+        sol_amount: u64,
+    ) -> Result<()> {
+        execute_buy(
+            ctx.accounts.buy_accounts(),
+            ctx.bumps.source_token_account,
+            sol_amount,
+            0,
+            ctx.remaining_accounts,
+        )
+    }
 
-        let sol_transfer_seeds = &[
-            constants::seeds::TOKEN_SEED,
-            name.as_bytes(),
-            &[ctx.bumps.token_info],
-        ];
-        let sol_transfer_signer = [&sol_transfer_seeds[..]];
+    pub fn sell_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellTokens<'info>>,
+        token_amount: u64,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.token_info.launched,
+            errors::CustomErrors::AlreadyLaunched
+        );
 
-        // Transfer sol amount to signer
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.token_info.to_account_info(),
-                    to: ctx.accounts.signer.to_account_info(),
-                },
-                &sol_transfer_signer,
-            ),
-            sol_amount_after_fee as u64,
+        let fee_in_bps = ctx.accounts.platform.discounted_fee_in_bps(
+            ctx.accounts.platform.sell_fee_bps,
+            ctx.accounts.trader_volume.volume_sol,
+            ctx.accounts
+                .platform_token_account
+                .as_ref()
+                .map_or(0, |platform_token_account| platform_token_account.amount),
         )?;
+        let quote =
+            ctx.accounts
+                .token_info
+                .quote_sell(token_amount, fee_in_bps, &ctx.accounts.platform)?;
+        let fee_amount = quote.fee_amount;
+        let sol_amount_after_fee = quote.sol_amount_after_fee;
+
+        ctx.accounts
+            .trader_volume
+            .record(ctx.accounts.signer.key(), quote.sol_amount)?;
+
+        ctx.accounts.token_info.trade_stats.record_sell(&quote)?;
+        ctx.accounts
+            .platform_stats
+            .trade_stats
+            .record_sell(&quote)?;
+
+        ctx.accounts.token_info.sol_reserve = quote.sol_reserve_after;
+        ctx.accounts.token_info.token_reserve = quote.token_reserve_after;
+        ctx.accounts.token_info.real_token_reserve = quote.real_token_reserve_after;
+
+        ctx.accounts.platform.accumulated_fees = ctx
+            .accounts
+            .platform
+            .accumulated_fees
+            .checked_add(fee_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        // Transfer sol amount to signer
+        // The token info account holds data, so its lamports are moved directly instead of through
+        // the system program
+        ctx.accounts.token_info.sub_lamports(sol_amount_after_fee)?;
+        ctx.accounts.signer.add_lamports(sol_amount_after_fee)?;
 
         // Transfer sol fee amount to platform
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.token_info.to_account_info(),
-                    to: ctx.accounts.platform.to_account_info(),
-                },
-                &sol_transfer_signer,
-            ),
-            fee_amount as u64,
-        )?;
+        ctx.accounts.token_info.sub_lamports(fee_amount)?;
+        ctx.accounts.platform.add_lamports(fee_amount)?;
 
         // Transfer tokens from user to token account
-        token::transfer(
+        utils::transfer_checked_with_hook(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                SplTransfer {
+                TransferChecked {
                     from: ctx.accounts.user_token_account.to_account_info().clone(),
+                    mint: ctx.accounts.mint.to_account_info().clone(),
                     to: ctx.accounts.source_token_account.to_account_info().clone(),
-                    authority: ctx.accounts.user_token_account.to_account_info().clone(),
+                    authority: ctx.accounts.signer.to_account_info().clone(),
                 },
-            ),
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
             token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        ctx.accounts.source_token_account.reload()?;
+        ctx.accounts.token_info.check_reserve_invariants(
+            &ctx.accounts.token_info.to_account_info(),
+            &ctx.accounts.source_token_account,
         )?;
 
+        if let Some(position) = ctx.accounts.position.as_mut() {
+            position.record_sell(
+                ctx.accounts.signer.key(),
+                ctx.accounts.mint.key(),
+                token_amount,
+                sol_amount_after_fee,
+            )?;
+        }
+
         let tokens_sold_event = events::TokensSold {
             token: ctx.accounts.mint.key(),
             by: ctx.accounts.signer.key(),
             amount: token_amount,
+            sol_amount: quote.sol_amount,
+            fee_amount,
+            fee_in_bps: quote.fee_in_bps,
+            price_impact_bps: quote.price_impact_bps,
         };
         emit!(tokens_sold_event);
 
         Ok(())
     }
 
-    pub fn add_liquidity(ctx: Context<AddLiquidity>, name: String) -> Result<()> {
+    pub fn quote_buy(ctx: Context<QuoteTrade>, sol_amount: u64) -> Result<BuyQuote> {
+        require!(
+            !ctx.accounts.token_info.launched,
+            errors::CustomErrors::AlreadyLaunched
+        );
+
+        let fee_in_bps = ctx
+            .accounts
+            .token_info
+            .buy_fee_in_bps(&ctx.accounts.platform, Clock::get()?.unix_timestamp)?;
+        let fee_in_bps = ctx.accounts.platform.discounted_fee_in_bps(
+            fee_in_bps,
+            ctx.accounts.trader_volume_sol(),
+            ctx.accounts.platform_token_balance(),
+        )?;
+
+        ctx.accounts
+            .token_info
+            .quote_buy(sol_amount, fee_in_bps, &ctx.accounts.platform)
+    }
+
+    pub fn quote_sell(ctx: Context<QuoteTrade>, token_amount: u64) -> Result<SellQuote> {
+        require!(
+            !ctx.accounts.token_info.launched,
+            errors::CustomErrors::AlreadyLaunched
+        );
+
+        let fee_in_bps = ctx.accounts.platform.discounted_fee_in_bps(
+            ctx.accounts.platform.sell_fee_bps,
+            ctx.accounts.trader_volume_sol(),
+            ctx.accounts.platform_token_balance(),
+        )?;
+
+        ctx.accounts
+            .token_info
+            .quote_sell(token_amount, fee_in_bps, &ctx.accounts.platform)
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        let position_closed_event = events::PositionClosed {
+            token: ctx.accounts.mint.key(),
+            owner: ctx.accounts.signer.key(),
+        };
+        emit!(position_closed_event);
+
+        Ok(())
+    }
+
+    pub fn add_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddLiquidity<'info>>,
+    ) -> Result<()> {
         require!(
             ctx.accounts.token_info.launched,
             errors::CustomErrors::NotLaunched
         );
 
-        let sol_amount = ctx.accounts.token_info.sol_reserve - ctx.accounts.token_info.virtual_sol;
-        let token_amount = utils::get_amount_using_spot_price(
+        let sol_amount = ctx
+            .accounts
+            .token_info
+            .sol_reserve
+            .checked_sub(ctx.accounts.token_info.virtual_sol)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        let token_amount = utils::get_spot_amount_out(
+            &ctx.accounts.token_info.curve_type,
             &(sol_amount as u128),
-            &(ctx.accounts.token_info.sol_reserve as u128),
-            &(ctx.accounts.token_info.token_reserve as u128),
-        );
+            &ctx.accounts.token_info.curve_reserves(),
+        )?;
 
-        let source_token_account_key = ctx.accounts.source_token_account.key();
         let mint_token_account_key = ctx.accounts.mint.key();
         let token_transfer_seeds = &[
             constants::seeds::TOKEN_ACCOUNT_SEED,
-            source_token_account_key.as_ref(),
             mint_token_account_key.as_ref(),
             &[ctx.bumps.source_token_account],
         ];
         let token_transfer_signer = [&token_transfer_seeds[..]];
 
         // Transfer token amount to admin
-        token::transfer(
+        utils::transfer_checked_with_hook(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                SplTransfer {
+                TransferChecked {
                     from: ctx.accounts.source_token_account.to_account_info().clone(),
+                    mint: ctx.accounts.mint.to_account_info().clone(),
                     to: ctx.accounts.user_token_account.to_account_info().clone(),
                     authority: ctx.accounts.source_token_account.to_account_info().clone(),
                 },
                 &token_transfer_signer,
-            ),
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
             token_amount,
+            ctx.accounts.mint.decimals,
         )?;
 
-        let sol_transfer_seeds = &[
-            constants::seeds::TOKEN_SEED,
-            name.as_ref(),
-            &[ctx.bumps.token_info],
-        ];
-        let sol_transfer_signer = [&sol_transfer_seeds[..]];
-
         // Transfer sol amount to platform owner
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.token_info.to_account_info(),
-                    to: ctx.accounts.signer.to_account_info(),
-                },
-                &sol_transfer_signer,
-            ),
-            sol_amount,
+        ctx.accounts.token_info.sub_lamports(sol_amount)?;
+        ctx.accounts.signer.add_lamports(sol_amount)?;
+
+        // Everything raised has left the curve, and whatever tokens remain are kept for the pool
+        let token_info = &mut ctx.accounts.token_info;
+        token_info.sol_reserve = token_info.virtual_sol;
+        token_info.pool_token_reserve = token_info
+            .real_token_reserve
+            .checked_add(token_info.pool_token_reserve)
+            .and_then(|remaining| remaining.checked_sub(token_amount))
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        token_info.real_token_reserve = 0;
+
+        ctx.accounts.source_token_account.reload()?;
+        ctx.accounts.token_info.check_reserve_invariants(
+            &ctx.accounts.token_info.to_account_info(),
+            &ctx.accounts.source_token_account,
         )?;
 
         let liquidity_added_event = events::LiquidityAdded {
@@ -434,12 +1026,426 @@ pub mod solana_pump_fun {
     }
 }
 
+// Shared instruction logic
+
+/// Accounts used by the steps shared by every token creation instruction.
+struct TokenCreationAccounts<'a, 'info> {
+    platform: &'a mut Account<'info, Platform>,
+    platform_stats: &'a mut Account<'info, PlatformStats>,
+    signer: &'a Signer<'info>,
+    mint: &'a mut InterfaceAccount<'info, Mint>,
+    token_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    token_info: &'a mut Account<'info, TokenInfo>,
+    curve_preset: Option<&'a Account<'info, CurvePreset>>,
+    creator_token_account: Option<&'a mut InterfaceAccount<'info, TokenAccount>>,
+    vesting: Option<&'a mut Account<'info, Vesting>>,
+    buyer_record: Option<&'a mut Account<'info, BuyerRecord>>,
+    trader_volume: Option<&'a mut Account<'info, TraderVolume>>,
+    position: Option<&'a mut Account<'info, Position>>,
+    platform_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+}
+
+/// Sets up the launch once its mint and metadata exist: initializes the token info, sets aside the
+/// creator allocation, mints the fixed supply to the vault and runs the creator's initial buy.
+fn finish_token_creation<'info>(
+    mut accounts: TokenCreationAccounts<'_, 'info>,
+    platform_bump: u8,
+    token_account_bump: u8,
+    create_token_params: &CreateTokenParams,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let seeds = &[constants::seeds::PLATFORM_SEED, &[platform_bump]];
+    let signer = [&seeds[..]];
+
+    // Second, initialize the token campaign params
+    accounts.token_info.initialize(
+        accounts.mint.key(),
+        accounts.signer.key(),
+        accounts.platform,
+        accounts.curve_preset,
+        create_token_params,
+    )?;
+
+    // Set aside the creator allocation, if any, which vests from the launch
+    if let Some(creator_vesting) = &create_token_params.creator_vesting {
+        let vesting = accounts
+            .vesting
+            .as_deref_mut()
+            .ok_or(errors::CustomErrors::MissingVestingAccount)?;
+        let total_amount = accounts
+            .token_info
+            .reserve_creator_allocation(creator_vesting.allocation_bps)?;
+        vesting.initialize(
+            accounts.mint.key(),
+            accounts.signer.key(),
+            total_amount,
+            creator_vesting,
+        )?;
+
+        let vesting_created_event = events::VestingCreated {
+            token: accounts.mint.key(),
+            creator: accounts.signer.key(),
+            total_amount,
+            cliff_time: vesting.cliff_time,
+            end_time: vesting.end_time,
+        };
+        emit!(vesting_created_event);
+    }
+
+    // Third, mint the total supply of the tokens to the token vault associated with this token launch
+    mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            MintTo {
+                authority: accounts.platform.to_account_info(),
+                to: accounts.token_account.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+            },
+            &signer,
+        ),
+        accounts.token_info.total_supply,
+    )?;
+
+    // The supply is fixed from now on, so the mint authority is revoked
+    set_authority(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            SetAuthority {
+                current_authority: accounts.platform.to_account_info(),
+                account_or_mint: accounts.mint.to_account_info(),
+            },
+            &signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    accounts.mint.reload()?;
+    require!(
+        accounts.mint.mint_authority.is_none() && accounts.mint.freeze_authority.is_none(),
+        errors::CustomErrors::MintNotFixed
+    );
+
+    // Finally, let the creator take the first position before anyone else can trade
+    if let Some(initial_buy_sol) = create_token_params.initial_buy_sol {
+        execute_initial_buy(
+            &mut accounts,
+            token_account_bump,
+            initial_buy_sol,
+            create_token_params.min_token_out,
+            remaining_accounts,
+        )?;
+    }
+
+    accounts.platform_stats.record_token_created()?;
+
+    let token_created_event = events::TokenCreated {
+        token: accounts.mint.key(),
+        total_supply: accounts.mint.supply,
+    };
+    emit!(token_created_event);
+
+    Ok(())
+}
+
+/// Stores the token metadata on a Token-2022 mint, whose metadata pointer points at itself.
+fn initialize_mint_metadata(
+    accounts: &TokenCreationAccounts,
+    platform_bump: u8,
+    create_token_params: &CreateTokenParams,
+) -> Result<()> {
+    let seeds = &[constants::seeds::PLATFORM_SEED, &[platform_bump]];
+    let signer = [&seeds[..]];
+
+    // First, store the token metadata on the mint itself
+    // The token program grows the mint to fit the metadata but does not pay for the extra
+    // space, so the mint is topped up to stay rent exempt beforehand
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(accounts.platform.key()))?,
+        mint: accounts.mint.key(),
+        name: create_token_params.name.clone(),
+        symbol: create_token_params.symbol.clone(),
+        uri: create_token_params.uri.clone(),
+        additional_metadata: vec![],
+    };
+    let mint_account = accounts.mint.to_account_info();
+    let mint_space = mint_account
+        .data_len()
+        .checked_add(token_metadata.tlv_size_of()?)
+        .ok_or(errors::CustomErrors::MathOverflow)?;
+    utils::top_up_rent(
+        &accounts.signer.to_account_info(),
+        &mint_account,
+        &accounts.system_program,
+        mint_space,
+    )?;
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            TokenMetadataInitialize {
+                token_program_id: accounts.token_program.clone(),
+                metadata: mint_account.clone(),
+                update_authority: accounts.platform.to_account_info(),
+                mint_authority: accounts.platform.to_account_info(),
+                mint: mint_account,
+            },
+            &signer,
+        ),
+        create_token_params.name.clone(),
+        create_token_params.symbol.clone(),
+        create_token_params.uri.clone(),
+    )?;
+
+    if accounts.platform.metadata_policy == MetadataPolicy::Immutable {
+        revoke_mint_metadata_authority(
+            &accounts.token_program,
+            &accounts.mint.to_account_info(),
+            &accounts.platform.to_account_info(),
+            &signer,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Drops the platform's update authority over Token-2022 metadata, after which the token program
+/// rejects any further metadata change.
+fn revoke_mint_metadata_authority<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    platform: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    token_metadata_update_authority(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataUpdateAuthority {
+                token_program_id: token_program.clone(),
+                metadata: mint.clone(),
+                current_authority: platform.clone(),
+                new_authority: platform.clone(),
+            },
+            signer,
+        ),
+        OptionalNonZeroPubkey::default(),
+    )
+}
+
+/// Runs the creator's initial buy exactly like a `buy_tokens` call made right after the launch.
+fn execute_initial_buy<'info>(
+    accounts: &mut TokenCreationAccounts<'_, 'info>,
+    token_account_bump: u8,
+    initial_buy_sol: u64,
+    min_token_out: u64,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let buy_accounts = BuyAccounts {
+        platform: accounts.platform,
+        platform_stats: accounts.platform_stats,
+        signer: accounts.signer,
+        mint: accounts.mint,
+        token_info: accounts.token_info,
+        vault: accounts.token_account,
+        buyer_token_account: accounts
+            .creator_token_account
+            .as_deref_mut()
+            .ok_or(errors::CustomErrors::MissingCreatorTokenAccount)?,
+        buyer_record: accounts
+            .buyer_record
+            .as_deref_mut()
+            .ok_or(errors::CustomErrors::MissingInitialBuyAccounts)?,
+        trader_volume: accounts
+            .trader_volume
+            .as_deref_mut()
+            .ok_or(errors::CustomErrors::MissingInitialBuyAccounts)?,
+        position: accounts.position.as_deref_mut(),
+        platform_token_account: accounts.platform_token_account,
+        system_program: accounts.system_program.clone(),
+        token_program: accounts.token_program.clone(),
+    };
+
+    execute_buy(
+        buy_accounts,
+        token_account_bump,
+        initial_buy_sol,
+        min_token_out,
+        remaining_accounts,
+    )
+}
+
+/// Accounts taking part in a buy on the curve.
+struct BuyAccounts<'a, 'info> {
+    platform: &'a mut Account<'info, Platform>,
+    platform_stats: &'a mut Account<'info, PlatformStats>,
+    signer: &'a Signer<'info>,
+    mint: &'a InterfaceAccount<'info, Mint>,
+    token_info: &'a mut Account<'info, TokenInfo>,
+    vault: &'a mut InterfaceAccount<'info, TokenAccount>,
+    buyer_token_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    buyer_record: &'a mut Account<'info, BuyerRecord>,
+    trader_volume: &'a mut Account<'info, TraderVolume>,
+    position: Option<&'a mut Account<'info, Position>>,
+    platform_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+}
+
+/// Buys from the curve with `sol_amount` (fee included), shared by `buy_tokens` and the creator's
+/// initial buy so that both go through the same fees, caps and accounting.
+fn execute_buy<'info>(
+    mut accounts: BuyAccounts<'_, 'info>,
+    vault_bump: u8,
+    sol_amount: u64,
+    min_token_out: u64,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        !accounts.token_info.launched,
+        errors::CustomErrors::AlreadyLaunched
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let fee_in_bps = accounts.token_info.buy_fee_in_bps(accounts.platform, now)?;
+    let fee_in_bps = accounts.platform.discounted_fee_in_bps(
+        fee_in_bps,
+        accounts.trader_volume.volume_sol,
+        accounts
+            .platform_token_account
+            .map_or(0, |platform_token_account| platform_token_account.amount),
+    )?;
+    let quote = accounts
+        .token_info
+        .quote_buy(sol_amount, fee_in_bps, accounts.platform)?;
+    require!(
+        quote.token_amount >= min_token_out,
+        errors::CustomErrors::SlippageExceeded
+    );
+    let fee_amount = quote.fee_amount;
+
+    // The trade counts towards the trader's tier from the next one on
+    accounts
+        .trader_volume
+        .record(accounts.signer.key(), sol_amount)?;
+
+    // Cap how much each wallet can spend while the launch window is open
+    let buyer_record = &mut accounts.buyer_record;
+    let new_buyer = buyer_record.buyer == Pubkey::default();
+    buyer_record.token = accounts.mint.key();
+    buyer_record.buyer = accounts.signer.key();
+    if accounts.token_info.in_launch_window(now) {
+        buyer_record.launch_sol_spent = buyer_record
+            .launch_sol_spent
+            .checked_add(sol_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        require!(
+            buyer_record.launch_sol_spent <= accounts.token_info.launch_window.max_sol_per_wallet,
+            errors::CustomErrors::LaunchWalletCapExceeded
+        );
+    }
+    let sol_amount_after_fee = quote.sol_amount_after_fee;
+    let token_amount = quote.token_amount;
+
+    accounts.token_info.apply_buy(&quote, accounts.platform)?;
+    accounts
+        .token_info
+        .trade_stats
+        .record_buy(&quote, new_buyer)?;
+    accounts
+        .platform_stats
+        .trade_stats
+        .record_buy(&quote, new_buyer)?;
+
+    // Transfer sol amount (after applying fee) from signer to token info account
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.clone(),
+            system_program::Transfer {
+                from: accounts.signer.to_account_info(),
+                to: accounts.token_info.to_account_info(),
+            },
+        ),
+        sol_amount_after_fee,
+    )?;
+
+    // Transfer fees (in sol) to the platform account
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.clone(),
+            system_program::Transfer {
+                from: accounts.signer.to_account_info(),
+                to: accounts.platform.to_account_info(),
+            },
+        ),
+        fee_amount,
+    )?;
+
+    let mint_token_account_key = accounts.mint.key();
+    let seeds = &[
+        constants::seeds::TOKEN_ACCOUNT_SEED,
+        mint_token_account_key.as_ref(),
+        &[vault_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    // Transfer tokens to user
+    utils::transfer_checked_with_hook(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            TransferChecked {
+                from: accounts.vault.to_account_info().clone(),
+                mint: accounts.mint.to_account_info().clone(),
+                to: accounts.buyer_token_account.to_account_info().clone(),
+                authority: accounts.vault.to_account_info().clone(),
+            },
+            &signer,
+        )
+        .with_remaining_accounts(remaining_accounts.to_vec()),
+        token_amount,
+        accounts.mint.decimals,
+    )?;
+
+    accounts.vault.reload()?;
+    accounts
+        .token_info
+        .check_reserve_invariants(&accounts.token_info.to_account_info(), accounts.vault)?;
+
+    accounts.buyer_token_account.reload()?;
+    if let Some(position) = accounts.position.as_deref_mut() {
+        position.record_buy(
+            accounts.signer.key(),
+            accounts.mint.key(),
+            sol_amount,
+            token_amount,
+        )?;
+    }
+    accounts
+        .token_info
+        .check_max_holding(accounts.buyer_token_account.amount)?;
+
+    let tokens_bought_event = events::TokensBought {
+        token: accounts.mint.key(),
+        by: accounts.signer.key(),
+        sol_amount,
+        token_amount,
+        fee_amount,
+        fee_in_bps: quote.fee_in_bps,
+        price_impact_bps: quote.price_impact_bps,
+    };
+    emit!(tokens_bought_event);
+
+    Ok(())
+}
+
 // Contexts
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(init, payer=signer, seeds=[constants::seeds::PLATFORM_SEED], bump, space=constants::general::DISCRIMINATOR_SIZE + Platform::INIT_SPACE)]
     pub platform: Account<'info, Platform>,
+    #[account(init, payer=signer, seeds=[constants::seeds::PLATFORM_STATS_SEED], bump, space=constants::general::DISCRIMINATOR_SIZE + PlatformStats::INIT_SPACE)]
+    pub platform_stats: Account<'info, PlatformStats>,
     #[account(mut)]
     pub signer: Signer<'info>,
 
@@ -465,41 +1471,280 @@ pub struct WithdrawFees<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(create_token_params: CreateTokenParams)]
 pub struct CreateToken<'info> {
     #[account(mut, seeds=[constants::seeds::PLATFORM_SEED], bump)]
     pub platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds=[constants::seeds::PLATFORM_STATS_SEED], bump)]
+    pub platform_stats: Box<Account<'info, PlatformStats>>,
     #[account(mut)]
     pub signer: Signer<'info>,
 
     /// CHECK: New Metaplex Account being created
     #[account(mut)]
     pub metadata: UncheckedAccount<'info>,
-    #[account(init, payer=signer, seeds=[constants::seeds::MINT_SEED, create_token_params.name.as_bytes()], bump, mint::decimals=constants::general::DECIMALS, mint::authority=mint)]
-    pub mint: Box<Account<'info, Mint>>,
-    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=token_account)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
-    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_SEED, create_token_params.name.as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TokenInfo::INIT_SPACE,)]
+    #[account(init, payer=signer, mint::decimals=constants::general::DECIMALS, mint::authority=platform, mint::token_program=token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=token_account, token::token_program=token_program)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TokenInfo::INIT_SPACE,)]
     pub token_info: Box<Account<'info, TokenInfo>>,
+    pub curve_preset: Option<Box<Account<'info, CurvePreset>>>,
+    #[account(init_if_needed, payer=signer, associated_token::mint=mint, associated_token::authority=signer, associated_token::token_program=token_program)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::VESTING_SEED, mint.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Vesting::INIT_SPACE)]
+    pub vesting: Option<Box<Account<'info, Vesting>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::BUYER_RECORD_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + BuyerRecord::INIT_SPACE)]
+    pub buyer_record: Option<Box<Account<'info, BuyerRecord>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::TRADER_VOLUME_SEED, signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TraderVolume::INIT_SPACE)]
+    pub trader_volume: Option<Box<Account<'info, TraderVolume>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::POSITION_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Position::INIT_SPACE)]
+    pub position: Option<Box<Account<'info, Position>>>,
+    /// Creator's platform token account, for the holder discount on the initial buy
+    #[account(token::authority=signer, constraint=platform.platform_token_mint == Some(platform_token_account.mint) @ errors::CustomErrors::InvalidPlatformTokenAccount)]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
     pub token_metadata_program: Program<'info, Metaplex>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+impl<'info> CreateToken<'info> {
+    fn token_creation_accounts(&mut self) -> TokenCreationAccounts<'_, 'info> {
+        TokenCreationAccounts {
+            platform: &mut self.platform,
+            platform_stats: &mut self.platform_stats,
+            signer: &self.signer,
+            mint: &mut self.mint,
+            token_account: &mut self.token_account,
+            token_info: &mut self.token_info,
+            curve_preset: self.curve_preset.as_deref(),
+            creator_token_account: self.creator_token_account.as_deref_mut(),
+            vesting: self.vesting.as_deref_mut(),
+            buyer_record: self.buyer_record.as_deref_mut(),
+            trader_volume: self.trader_volume.as_deref_mut(),
+            position: self.position.as_deref_mut(),
+            platform_token_account: self.platform_token_account.as_deref(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateToken2022<'info> {
+    #[account(mut, seeds=[constants::seeds::PLATFORM_SEED], bump)]
+    pub platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds=[constants::seeds::PLATFORM_STATS_SEED], bump)]
+    pub platform_stats: Box<Account<'info, PlatformStats>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(init, payer=signer, mint::decimals=constants::general::DECIMALS, mint::authority=platform, mint::token_program=token_program, extensions::metadata_pointer::authority=platform, extensions::metadata_pointer::metadata_address=mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=token_account, token::token_program=token_program)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TokenInfo::INIT_SPACE,)]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    pub curve_preset: Option<Box<Account<'info, CurvePreset>>>,
+    #[account(init_if_needed, payer=signer, associated_token::mint=mint, associated_token::authority=signer, associated_token::token_program=token_program)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::VESTING_SEED, mint.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Vesting::INIT_SPACE)]
+    pub vesting: Option<Box<Account<'info, Vesting>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::BUYER_RECORD_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + BuyerRecord::INIT_SPACE)]
+    pub buyer_record: Option<Box<Account<'info, BuyerRecord>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::TRADER_VOLUME_SEED, signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TraderVolume::INIT_SPACE)]
+    pub trader_volume: Option<Box<Account<'info, TraderVolume>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::POSITION_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Position::INIT_SPACE)]
+    pub position: Option<Box<Account<'info, Position>>>,
+    /// Creator's platform token account, for the holder discount on the initial buy
+    #[account(token::authority=signer, constraint=platform.platform_token_mint == Some(platform_token_account.mint) @ errors::CustomErrors::InvalidPlatformTokenAccount)]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+impl<'info> CreateToken2022<'info> {
+    fn token_creation_accounts(&mut self) -> TokenCreationAccounts<'_, 'info> {
+        TokenCreationAccounts {
+            platform: &mut self.platform,
+            platform_stats: &mut self.platform_stats,
+            signer: &self.signer,
+            mint: &mut self.mint,
+            token_account: &mut self.token_account,
+            token_info: &mut self.token_info,
+            curve_preset: self.curve_preset.as_deref(),
+            creator_token_account: self.creator_token_account.as_deref_mut(),
+            vesting: self.vesting.as_deref_mut(),
+            buyer_record: self.buyer_record.as_deref_mut(),
+            trader_volume: self.trader_volume.as_deref_mut(),
+            position: self.position.as_deref_mut(),
+            platform_token_account: self.platform_token_account.as_deref(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateToken2022WithHook<'info> {
+    #[account(mut, seeds=[constants::seeds::PLATFORM_SEED], bump)]
+    pub platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds=[constants::seeds::PLATFORM_STATS_SEED], bump)]
+    pub platform_stats: Box<Account<'info, PlatformStats>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Program invoked on every transfer of the token, only needs to be executable
+    #[account(executable)]
+    pub transfer_hook_program: UncheckedAccount<'info>,
+    #[account(init, payer=signer, mint::decimals=constants::general::DECIMALS, mint::authority=platform, mint::token_program=token_program, extensions::metadata_pointer::authority=platform, extensions::metadata_pointer::metadata_address=mint, extensions::transfer_hook::authority=platform, extensions::transfer_hook::program_id=transfer_hook_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=token_account, token::token_program=token_program)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TokenInfo::INIT_SPACE,)]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    pub curve_preset: Option<Box<Account<'info, CurvePreset>>>,
+    #[account(init_if_needed, payer=signer, associated_token::mint=mint, associated_token::authority=signer, associated_token::token_program=token_program)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(init, payer=signer, seeds=[constants::seeds::VESTING_SEED, mint.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Vesting::INIT_SPACE)]
+    pub vesting: Option<Box<Account<'info, Vesting>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::BUYER_RECORD_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + BuyerRecord::INIT_SPACE)]
+    pub buyer_record: Option<Box<Account<'info, BuyerRecord>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::TRADER_VOLUME_SEED, signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TraderVolume::INIT_SPACE)]
+    pub trader_volume: Option<Box<Account<'info, TraderVolume>>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::POSITION_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Position::INIT_SPACE)]
+    pub position: Option<Box<Account<'info, Position>>>,
+    /// Creator's platform token account, for the holder discount on the initial buy
+    #[account(token::authority=signer, constraint=platform.platform_token_mint == Some(platform_token_account.mint) @ errors::CustomErrors::InvalidPlatformTokenAccount)]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+impl<'info> CreateToken2022WithHook<'info> {
+    fn token_creation_accounts(&mut self) -> TokenCreationAccounts<'_, 'info> {
+        TokenCreationAccounts {
+            platform: &mut self.platform,
+            platform_stats: &mut self.platform_stats,
+            signer: &self.signer,
+            mint: &mut self.mint,
+            token_account: &mut self.token_account,
+            token_info: &mut self.token_info,
+            curve_preset: self.curve_preset.as_deref(),
+            creator_token_account: self.creator_token_account.as_deref_mut(),
+            vesting: self.vesting.as_deref_mut(),
+            buyer_record: self.buyer_record.as_deref_mut(),
+            trader_volume: self.trader_volume.as_deref_mut(),
+            position: self.position.as_deref_mut(),
+            platform_token_account: self.platform_token_account.as_deref(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(curve_preset_params: CurvePresetParams)]
+pub struct CreateCurvePreset<'info> {
+    #[account(seeds=[constants::seeds::PLATFORM_SEED], bump, constraint=platform.owner == signer.key() @ errors::CustomErrors::NotOwner)]
+    pub platform: Account<'info, Platform>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(init, payer=signer, seeds=[constants::seeds::CURVE_PRESET_SEED, curve_preset_params.name.as_bytes()], bump, space=constants::general::DISCRIMINATOR_SIZE + CurvePreset::INIT_SPACE)]
+    pub curve_preset: Account<'info, CurvePreset>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCurvePreset<'info> {
+    #[account(seeds=[constants::seeds::PLATFORM_SEED], bump, constraint=platform.owner == signer.key() @ errors::CustomErrors::NotOwner)]
+    pub platform: Account<'info, Platform>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut, close=signer, seeds=[constants::seeds::CURVE_PRESET_SEED, curve_preset.name.as_bytes()], bump)]
+    pub curve_preset: Account<'info, CurvePreset>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(seeds=[constants::seeds::PLATFORM_SEED], bump)]
+    pub platform: Box<Account<'info, Platform>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut, mint::token_program=token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, constraint=mint.key() == token_info.token, constraint=token_info.creator == signer.key() @ errors::CustomErrors::NotCreator)]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    /// CHECK: Metaplex metadata of the token, only needed for tokens created through `create_token`
+    #[account(mut, seeds=[constants::seeds::METADATA_SEED, Metaplex::id().as_ref(), mint.key().as_ref()], bump, seeds::program=Metaplex::id())]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Option<Program<'info, Metaplex>>,
+}
+
+#[derive(Accounts)]
+pub struct LockTokenMetadata<'info> {
+    #[account(seeds=[constants::seeds::PLATFORM_SEED], bump)]
+    pub platform: Box<Account<'info, Platform>>,
+    pub signer: Signer<'info>,
+
+    #[account(mut, mint::token_program=token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, constraint=mint.key() == token_info.token)]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    /// CHECK: Metaplex metadata of the token, only needed for tokens created through `create_token`
+    #[account(mut, seeds=[constants::seeds::METADATA_SEED, Metaplex::id().as_ref(), mint.key().as_ref()], bump, seeds::program=Metaplex::id())]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Option<Program<'info, Metaplex>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account()]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, constraint=mint.key() == token_info.token)]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    #[account(mut, seeds=[constants::seeds::VESTING_SEED, mint.key().as_ref()], bump, constraint=vesting.creator == signer.key() @ errors::CustomErrors::NotCreator)]
+    pub vesting: Box<Account<'info, Vesting>>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=source_token_account, token::token_program=token_program)]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(init_if_needed, payer=signer, associated_token::mint=mint, associated_token::authority=signer, associated_token::token_program=token_program)]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct InitAccountsForToken<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account()]
-    pub mint: Account<'info, Mint>,
-    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, signer.key().as_ref(), mint.key().as_ref()], bump,  token::mint=mint, token::authority=signer)]
-    pub token_account: Account<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(init, payer=signer, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, signer.key().as_ref(), mint.key().as_ref()], bump,  token::mint=mint, token::authority=signer, token::token_program=token_program)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -507,45 +1752,127 @@ pub struct InitAccountsForToken<'info> {
 pub struct BuyTokens<'info> {
     #[account(mut, seeds=[constants::seeds::PLATFORM_SEED], bump)]
     pub platform: Account<'info, Platform>,
+    #[account(mut, seeds=[constants::seeds::PLATFORM_STATS_SEED], bump)]
+    pub platform_stats: Box<Account<'info, PlatformStats>>,
     #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account()]
-    pub mint: Account<'info, Mint>,
-    #[account(mut, constraint=mint.key() == token_info.token)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, constraint=mint.key() == token_info.token)]
     pub token_info: Account<'info, TokenInfo>,
-    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=source_token_account)]
-    pub source_token_account: Account<'info, TokenAccount>,
-    #[account(mut, associated_token::mint=mint, associated_token::authority=signer)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=source_token_account, token::token_program=token_program)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint=mint, associated_token::authority=signer, associated_token::token_program=token_program)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::BUYER_RECORD_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + BuyerRecord::INIT_SPACE)]
+    pub buyer_record: Box<Account<'info, BuyerRecord>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::TRADER_VOLUME_SEED, signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TraderVolume::INIT_SPACE)]
+    pub trader_volume: Box<Account<'info, TraderVolume>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::POSITION_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Position::INIT_SPACE)]
+    pub position: Option<Box<Account<'info, Position>>>,
+    /// Trader's platform token account, for the platform token holder discount
+    #[account(token::authority=signer, constraint=platform.platform_token_mint == Some(platform_token_account.mint) @ errors::CustomErrors::InvalidPlatformTokenAccount)]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> BuyTokens<'info> {
+    fn buy_accounts(&mut self) -> BuyAccounts<'_, 'info> {
+        BuyAccounts {
+            platform: &mut self.platform,
+            platform_stats: &mut self.platform_stats,
+            signer: &self.signer,
+            mint: &self.mint,
+            token_info: &mut self.token_info,
+            vault: &mut self.source_token_account,
+            buyer_token_account: &mut self.user_token_account,
+            buyer_record: &mut self.buyer_record,
+            trader_volume: &mut self.trader_volume,
+            position: self.position.as_deref_mut(),
+            platform_token_account: self.platform_token_account.as_deref(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
 }
 
 #[derive(Accounts)]
-#[instruction(name: String)]
 pub struct SellTokens<'info> {
     #[account(mut, seeds=[constants::seeds::PLATFORM_SEED], bump)]
     pub platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds=[constants::seeds::PLATFORM_STATS_SEED], bump)]
+    pub platform_stats: Box<Account<'info, PlatformStats>>,
     #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account()]
-    pub mint: Box<Account<'info, Mint>>,
-    #[account(mut, seeds=[constants::seeds::TOKEN_SEED, name.as_bytes()], bump, constraint=mint.key() == token_info.token)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, constraint=mint.key() == token_info.token)]
     pub token_info: Box<Account<'info, TokenInfo>>,
-    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=source_token_account)]
-    pub source_token_account: Box<Account<'info, TokenAccount>>,
-    #[account(mut, associated_token::mint=mint, associated_token::authority=signer)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump, token::mint=mint, token::authority=source_token_account, token::token_program=token_program)]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, associated_token::mint=mint, associated_token::authority=signer, associated_token::token_program=token_program)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::TRADER_VOLUME_SEED, signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + TraderVolume::INIT_SPACE)]
+    pub trader_volume: Box<Account<'info, TraderVolume>>,
+    #[account(init_if_needed, payer=signer, seeds=[constants::seeds::POSITION_SEED, mint.key().as_ref(), signer.key().as_ref()], bump, space=constants::general::DISCRIMINATOR_SIZE + Position::INIT_SPACE)]
+    pub position: Option<Box<Account<'info, Position>>>,
+    /// Trader's platform token account, for the platform token holder discount
+    #[account(token::authority=signer, constraint=platform.platform_token_mint == Some(platform_token_account.mint) @ errors::CustomErrors::InvalidPlatformTokenAccount)]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    #[account(seeds=[constants::seeds::PLATFORM_SEED], bump)]
+    pub platform: Account<'info, Platform>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, constraint=mint.key() == token_info.token)]
+    pub token_info: Account<'info, TokenInfo>,
+    /// CHECK: Only the key is used, to tie the optional accounts below to the trader being quoted
+    pub trader: UncheckedAccount<'info>,
+    /// Volume of the trader being quoted, if they have traded before
+    #[account(seeds=[constants::seeds::TRADER_VOLUME_SEED, trader.key().as_ref()], bump)]
+    pub trader_volume: Option<Account<'info, TraderVolume>>,
+    /// Platform token account of the trader being quoted, if they hold the platform token
+    #[account(token::authority=trader, constraint=platform.platform_token_mint == Some(platform_token_account.mint) @ errors::CustomErrors::InvalidPlatformTokenAccount)]
+    pub platform_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+impl QuoteTrade<'_> {
+    pub fn trader_volume_sol(&self) -> u64 {
+        self.trader_volume
+            .as_ref()
+            .map_or(0, |trader_volume| trader_volume.volume_sol)
+    }
+
+    pub fn platform_token_balance(&self) -> u64 {
+        self.platform_token_account
+            .as_ref()
+            .map_or(0, |platform_token_account| platform_token_account.amount)
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, close=signer, seeds=[constants::seeds::POSITION_SEED, mint.key().as_ref(), signer.key().as_ref()], bump)]
+    pub position: Account<'info, Position>,
 }
 
 #[derive(Accounts)]
-#[instruction(name: String)]
 pub struct AddLiquidity<'info> {
     #[account(mut, seeds=[constants::seeds::PLATFORM_SEED], bump)]
     pub platform: Account<'info, Platform>,
@@ -553,16 +1880,16 @@ pub struct AddLiquidity<'info> {
     pub signer: Signer<'info>,
 
     #[account()]
-    pub mint: Account<'info, Mint>,
-    #[account(mut, seeds=[constants::seeds::TOKEN_SEED, name.as_ref()], bump, constraint=mint.key() == token_info.token)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_SEED, mint.key().as_ref()], bump, constraint=mint.key() == token_info.token)]
     pub token_info: Account<'info, TokenInfo>,
-    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump,  token::mint=mint, token::authority=source_token_account)]
-    pub source_token_account: Account<'info, TokenAccount>,
-    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, signer.key().as_ref(), mint.key().as_ref()], bump, token::mint=mint, token::authority=signer)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, mint.key().as_ref()], bump,  token::mint=mint, token::authority=source_token_account, token::token_program=token_program)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds=[constants::seeds::TOKEN_ACCOUNT_SEED, signer.key().as_ref(), mint.key().as_ref()], bump, token::mint=mint, token::authority=signer, token::token_program=token_program)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 // Accounts
@@ -571,11 +1898,66 @@ pub struct AddLiquidity<'info> {
 #[derive(InitSpace)]
 pub struct Platform {
     pub owner: Pubkey,
-    pub fee_in_bps: u64,
+    pub buy_fee_bps: u64,
+    pub sell_fee_bps: u64,
     pub accumulated_fees: u64,
     pub total_supply: u64,
     pub virtual_sol: u64,
+    pub virtual_token_reserve: u64,
+    pub real_token_reserve: u64,
     pub target_pool_balance: u64,
+    pub curve_type: CurveType,
+    pub curve_bounds: CurveBounds,
+    pub min_buy_sol_amount: u64,
+    pub min_sell_token_amount: u64,
+    pub metadata_policy: MetadataPolicy,
+    pub metadata_update_window: i64,
+    pub launch_window: LaunchWindow,
+    pub max_holding_bps: u64,
+    pub price_impact_fee: PriceImpactFee,
+    #[max_len(8)]
+    pub fee_tiers: Vec<FeeTier>,
+    pub platform_token_mint: Option<Pubkey>,
+    #[max_len(8)]
+    pub holder_discount_tiers: Vec<HolderDiscountTier>,
+}
+
+impl Platform {
+    pub fn curve_config(&self) -> CurveConfig {
+        CurveConfig {
+            curve_type: self.curve_type,
+            total_supply: self.total_supply,
+            virtual_sol: self.virtual_sol,
+            virtual_token_reserve: self.virtual_token_reserve,
+            real_token_reserve: self.real_token_reserve,
+            target_pool_balance: self.target_pool_balance,
+        }
+    }
+
+    /// Applies the discount of the highest fee tier reached by `volume_sol` to `fee_in_bps`, then
+    /// the discount of the highest holder tier reached by `platform_token_balance`.
+    pub fn discounted_fee_in_bps(
+        &self,
+        fee_in_bps: u64,
+        volume_sol: u64,
+        platform_token_balance: u64,
+    ) -> Result<u64> {
+        let volume_discount_bps = self
+            .fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| volume_sol >= tier.min_volume_sol)
+            .map_or(0, |tier| tier.discount_bps);
+        let holder_discount_bps = self
+            .holder_discount_tiers
+            .iter()
+            .rev()
+            .find(|tier| platform_token_balance >= tier.min_balance)
+            .map_or(0, |tier| tier.discount_bps);
+
+        let fee_in_bps = utils::apply_fee_discount(&fee_in_bps, &volume_discount_bps)?;
+        utils::apply_fee_discount(&fee_in_bps, &holder_discount_bps)
+    }
 }
 
 #[account]
@@ -585,10 +1967,852 @@ pub struct TokenInfo {
     pub creator: Pubkey,
     pub total_supply: u64,
     pub virtual_sol: u64,
+    pub virtual_token_reserve: u64,
     pub sol_reserve: u64,
     pub token_reserve: u64,
+    pub real_token_reserve: u64,
+    pub pool_token_reserve: u64,
     pub target_pool_balance: u64,
     pub launched: bool,
+    pub curve_type: CurveType,
+    pub curve_preset: Pubkey,
+    /// Last moment the creator can update the metadata, zero once the metadata is locked
+    pub metadata_update_deadline: i64,
+    pub vesting_token_reserve: u64,
+    pub created_at: i64,
+    pub launch_window: LaunchWindow,
+    /// Share of the total supply a wallet may hold until the token launches, zero for no cap
+    pub max_holding_bps: u64,
+    pub trade_stats: TradeStats,
+}
+
+impl TokenInfo {
+    /// Sets up the curve of a new token from the selected preset (or the platform defaults),
+    /// applying the creator's overrides if any.
+    pub fn initialize(
+        &mut self,
+        token: Pubkey,
+        creator: Pubkey,
+        platform: &Platform,
+        curve_preset: Option<&Account<CurvePreset>>,
+        create_token_params: &CreateTokenParams,
+    ) -> Result<()> {
+        let config = match curve_preset {
+            Some(curve_preset) => curve_preset.config.clone(),
+            None => platform.curve_config(),
+        };
+        let bounds = &platform.curve_bounds;
+        let total_supply = utils::resolve_curve_param(
+            create_token_params.total_supply,
+            config.total_supply,
+            bounds.min_total_supply,
+            bounds.max_total_supply,
+        )?;
+        let virtual_sol = utils::resolve_curve_param(
+            create_token_params.virtual_sol,
+            config.virtual_sol,
+            bounds.min_virtual_sol,
+            bounds.max_virtual_sol,
+        )?;
+        let target_pool_balance = utils::resolve_curve_param(
+            create_token_params.target_pool_balance,
+            config.target_pool_balance,
+            bounds.min_target_pool_balance,
+            bounds.max_target_pool_balance,
+        )?;
+        require!(
+            target_pool_balance > virtual_sol,
+            errors::CustomErrors::CurveParamOutOfBounds
+        );
+
+        // The token reserves keep the platform's proportions of the total supply
+        let (virtual_token_reserve, real_token_reserve) = if total_supply == config.total_supply {
+            (config.virtual_token_reserve, config.real_token_reserve)
+        } else {
+            (
+                utils::mul_div_floor(
+                    &(config.virtual_token_reserve as u128),
+                    &(total_supply as u128),
+                    &(config.total_supply as u128),
+                )?,
+                utils::mul_div_floor(
+                    &(config.real_token_reserve as u128),
+                    &(total_supply as u128),
+                    &(config.total_supply as u128),
+                )?,
+            )
+        };
+
+        self.token = token;
+        self.creator = creator;
+        self.total_supply = total_supply;
+        self.virtual_sol = virtual_sol;
+        self.sol_reserve = virtual_sol;
+        self.virtual_token_reserve = virtual_token_reserve;
+        self.token_reserve = virtual_token_reserve;
+        self.real_token_reserve = real_token_reserve;
+        self.pool_token_reserve = total_supply
+            .checked_sub(real_token_reserve)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        self.target_pool_balance = target_pool_balance;
+        self.curve_type = create_token_params.curve_type.unwrap_or(config.curve_type);
+        self.curve_preset = curve_preset
+            .map(|curve_preset| curve_preset.key())
+            .unwrap_or_default();
+        // Creators may tighten the platform's holding cap but never loosen or lift it
+        self.max_holding_bps = match create_token_params.max_holding_bps {
+            Some(max_holding_bps) => {
+                require!(
+                    max_holding_bps > 0
+                        && max_holding_bps <= constants::general::BPS as u64
+                        && (platform.max_holding_bps == 0
+                            || max_holding_bps <= platform.max_holding_bps),
+                    errors::CustomErrors::MaxHoldingLoosened
+                );
+                max_holding_bps
+            }
+            None => platform.max_holding_bps,
+        };
+        self.created_at = Clock::get()?.unix_timestamp;
+        self.launch_window = platform.launch_window.clone();
+        self.metadata_update_deadline = match platform.metadata_policy {
+            MetadataPolicy::Immutable => 0,
+            MetadataPolicy::CreatorUpdatable => self
+                .created_at
+                .checked_add(platform.metadata_update_window)
+                .ok_or(errors::CustomErrors::MathOverflow)?,
+        };
+
+        Ok(())
+    }
+
+    pub fn curve_reserves(&self) -> utils::CurveReserves {
+        utils::CurveReserves {
+            sol_reserve: self.sol_reserve as u128,
+            token_reserve: self.token_reserve as u128,
+            virtual_sol: self.virtual_sol as u128,
+            initial_token_reserve: self.virtual_token_reserve as u128,
+        }
+    }
+
+    /// Checks that the sol and tokens custodied for this curve still cover its accounting once a
+    /// trade or the graduation has moved funds. Anyone can send lamports or tokens to these
+    /// accounts, so custody is allowed to exceed the accounting but never to fall short of it.
+    pub fn check_reserve_invariants(
+        &self,
+        token_info: &AccountInfo,
+        source_token_account: &TokenAccount,
+    ) -> Result<()> {
+        let raised_sol = self
+            .sol_reserve
+            .checked_sub(self.virtual_sol)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        let required_lamports = Rent::get()?
+            .minimum_balance(token_info.data_len())
+            .checked_add(raised_sol)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        let required_tokens = self
+            .real_token_reserve
+            .checked_add(self.pool_token_reserve)
+            .and_then(|required| required.checked_add(self.vesting_token_reserve))
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        require!(
+            token_info.lamports() >= required_lamports,
+            errors::CustomErrors::InvariantViolated
+        );
+        require!(
+            source_token_account.amount >= required_tokens,
+            errors::CustomErrors::InvariantViolated
+        );
+
+        Ok(())
+    }
+
+    /// Moves `allocation_bps` of the total supply out of the curve into the creator's vesting
+    /// allocation. The curve starts with that many fewer tokens, so it prices the launch as if
+    /// they had never been for sale.
+    pub fn reserve_creator_allocation(&mut self, allocation_bps: u64) -> Result<u64> {
+        require!(
+            allocation_bps > 0
+                && allocation_bps <= constants::general::MAX_CREATOR_ALLOCATION_IN_BPS,
+            errors::CustomErrors::ExcessiveCreatorAllocation
+        );
+
+        let allocation = utils::mul_div_floor(
+            &(self.total_supply as u128),
+            &(allocation_bps as u128),
+            &(constants::general::BPS as u128),
+        )?;
+        require!(
+            allocation < self.real_token_reserve,
+            errors::CustomErrors::ExcessiveCreatorAllocation
+        );
+
+        self.real_token_reserve -= allocation;
+        self.virtual_token_reserve = self
+            .virtual_token_reserve
+            .checked_sub(allocation)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        self.token_reserve = self.virtual_token_reserve;
+        self.vesting_token_reserve = allocation;
+
+        Ok(allocation)
+    }
+
+    /// Records a buy computed by `quote_buy`, launching the token if the target market cap has been
+    /// hit or the curve sold out.
+    pub fn apply_buy(&mut self, quote: &BuyQuote, platform: &mut Platform) -> Result<()> {
+        platform.accumulated_fees = platform
+            .accumulated_fees
+            .checked_add(quote.fee_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        self.sol_reserve = quote.sol_reserve_after;
+        self.token_reserve = quote.token_reserve_after;
+        self.real_token_reserve = quote.real_token_reserve_after;
+
+        if self.sol_reserve == self.target_pool_balance || self.real_token_reserve == 0 {
+            self.launched = true;
+        }
+
+        Ok(())
+    }
+
+    /// Checks a wallet's balance after a buy against the holding cap. Buys only happen before
+    /// launch, so this ignores `launched`, which the buy completing the curve has already set.
+    pub fn check_max_holding(&self, balance: u64) -> Result<()> {
+        if self.max_holding_bps == 0 {
+            return Ok(());
+        }
+
+        let max_holding = utils::mul_div_floor(
+            &(self.total_supply as u128),
+            &(self.max_holding_bps as u128),
+            &(constants::general::BPS as u128),
+        )?;
+        require!(
+            balance <= max_holding,
+            errors::CustomErrors::MaxHoldingExceeded
+        );
+
+        Ok(())
+    }
+
+    pub fn in_launch_window(&self, now: i64) -> bool {
+        now < self.created_at.saturating_add(self.launch_window.duration)
+    }
+
+    /// Fee charged on buys at `now`. During the launch window it decays linearly from the launch
+    /// fee down to the platform buy fee.
+    pub fn buy_fee_in_bps(&self, platform: &Platform, now: i64) -> Result<u64> {
+        let launch_fee_in_bps = self.launch_window.start_fee_in_bps;
+        if !self.in_launch_window(now) || launch_fee_in_bps <= platform.buy_fee_bps {
+            return Ok(platform.buy_fee_bps);
+        }
+
+        let elapsed = now.saturating_sub(self.created_at).max(0);
+        let decayed_fee_in_bps = utils::mul_div_floor(
+            &((launch_fee_in_bps - platform.buy_fee_bps) as u128),
+            &(elapsed as u128),
+            &(self.launch_window.duration as u128),
+        )?;
+
+        Ok(launch_fee_in_bps - decayed_fee_in_bps)
+    }
+
+    /// Computes the outcome of buying with `sol_amount` (fee included) against the current state.
+    /// The fee starts at `base_fee_in_bps` and grows with the price impact of the trade at that
+    /// fee, following the platform's schedule.
+    pub fn quote_buy(
+        &self,
+        sol_amount: u64,
+        base_fee_in_bps: u64,
+        platform: &Platform,
+    ) -> Result<BuyQuote> {
+        let quote = self.quote_buy_at_fee(sol_amount, base_fee_in_bps, platform)?;
+        let fee_in_bps = utils::calculate_impact_fee_in_bps(
+            &base_fee_in_bps,
+            &quote.price_impact_bps,
+            &platform.price_impact_fee,
+        )?;
+        if fee_in_bps == base_fee_in_bps {
+            return Ok(quote);
+        }
+
+        self.quote_buy_at_fee(sol_amount, fee_in_bps, platform)
+    }
+
+    fn quote_buy_at_fee(
+        &self,
+        sol_amount: u64,
+        fee_in_bps: u64,
+        platform: &Platform,
+    ) -> Result<BuyQuote> {
+        require!(
+            sol_amount > 0 && sol_amount >= platform.min_buy_sol_amount,
+            errors::CustomErrors::TradeTooSmall
+        );
+
+        let reserves = self.curve_reserves();
+
+        // sol_amount = 101
+        // buy_fee = (101 * 100) / (10000 + 100) = 1
+        // sol_amount_after_fee = 100
+        let fee_amount = utils::calculate_buy_fee(&(sol_amount as u128), &(fee_in_bps as u128))?;
+        let sol_amount_after_fee = sol_amount
+            .checked_sub(fee_amount)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        let token_amount = utils::get_buy_amount_out(
+            &self.curve_type,
+            &(sol_amount_after_fee as u128),
+            &reserves,
+        )?;
+
+        require!(token_amount > 0, errors::CustomErrors::ZeroTradeOutput);
+        require!(
+            token_amount <= self.real_token_reserve,
+            errors::CustomErrors::RealTokenReserveExceeded
+        );
+
+        let sol_reserve_after = self
+            .sol_reserve
+            .checked_add(sol_amount_after_fee)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        let token_reserve_after = self
+            .token_reserve
+            .checked_sub(token_amount)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+
+        require!(
+            sol_reserve_after <= self.target_pool_balance,
+            errors::CustomErrors::BondingCurveBreached
+        );
+
+        let reserves_after = utils::CurveReserves {
+            sol_reserve: sol_reserve_after as u128,
+            token_reserve: token_reserve_after as u128,
+            ..reserves
+        };
+        let price_impact_bps = utils::get_price_impact_bps(
+            &utils::get_spot_price(&self.curve_type, &reserves)?,
+            &utils::get_spot_price(&self.curve_type, &reserves_after)?,
+        )?;
+
+        Ok(BuyQuote {
+            sol_amount,
+            fee_in_bps,
+            fee_amount,
+            sol_amount_after_fee,
+            token_amount,
+            price_impact_bps,
+            sol_reserve_after,
+            token_reserve_after,
+            real_token_reserve_after: self
+                .real_token_reserve
+                .checked_sub(token_amount)
+                .ok_or(errors::CustomErrors::MathUnderflow)?,
+        })
+    }
+
+    /// Computes the outcome of selling `token_amount` against the current state. The fee starts at
+    /// `base_fee_in_bps` and grows with the price impact of the trade, following the platform's
+    /// schedule.
+    pub fn quote_sell(
+        &self,
+        token_amount: u64,
+        base_fee_in_bps: u64,
+        platform: &Platform,
+    ) -> Result<SellQuote> {
+        require!(
+            token_amount > 0 && token_amount >= platform.min_sell_token_amount,
+            errors::CustomErrors::TradeTooSmall
+        );
+
+        let reserves = self.curve_reserves();
+
+        let sol_amount =
+            utils::get_sell_amount_out(&self.curve_type, &(token_amount as u128), &reserves)?;
+
+        let sol_reserve_after = self
+            .sol_reserve
+            .checked_sub(sol_amount)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+        let token_reserve_after = self
+            .token_reserve
+            .checked_add(token_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        let reserves_after = utils::CurveReserves {
+            sol_reserve: sol_reserve_after as u128,
+            token_reserve: token_reserve_after as u128,
+            ..reserves
+        };
+        let price_impact_bps = utils::get_price_impact_bps(
+            &utils::get_spot_price(&self.curve_type, &reserves)?,
+            &utils::get_spot_price(&self.curve_type, &reserves_after)?,
+        )?;
+
+        let fee_in_bps = utils::calculate_impact_fee_in_bps(
+            &base_fee_in_bps,
+            &price_impact_bps,
+            &platform.price_impact_fee,
+        )?;
+        let fee_amount = utils::calculate_sell_fee(&(sol_amount as u128), &(fee_in_bps as u128))?;
+        let sol_amount_after_fee = sol_amount
+            .checked_sub(fee_amount)
+            .ok_or(errors::CustomErrors::MathUnderflow)?;
+
+        require!(
+            sol_amount_after_fee > 0,
+            errors::CustomErrors::ZeroTradeOutput
+        );
+
+        Ok(SellQuote {
+            token_amount,
+            sol_amount,
+            fee_in_bps,
+            fee_amount,
+            sol_amount_after_fee,
+            price_impact_bps,
+            sol_reserve_after,
+            token_reserve_after,
+            real_token_reserve_after: self
+                .real_token_reserve
+                .checked_add(token_amount)
+                .ok_or(errors::CustomErrors::MathOverflow)?,
+        })
+    }
+}
+
+/// Platform-wide totals across all tokens.
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformStats {
+    pub trade_stats: TradeStats,
+    pub tokens_created: u64,
+}
+
+impl PlatformStats {
+    pub fn record_token_created(&mut self) -> Result<()> {
+        self.tokens_created = self
+            .tokens_created
+            .checked_add(1)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// Tracks a wallet's buys of a token.
+#[account]
+#[derive(InitSpace)]
+pub struct BuyerRecord {
+    pub token: Pubkey,
+    pub buyer: Pubkey,
+    pub launch_sol_spent: u64,
+}
+
+/// Cumulative sol traded by a wallet across all tokens, used to pick its fee tier.
+#[account]
+#[derive(InitSpace)]
+pub struct TraderVolume {
+    pub trader: Pubkey,
+    pub volume_sol: u64,
+}
+
+impl TraderVolume {
+    pub fn record(&mut self, trader: Pubkey, sol_amount: u64) -> Result<()> {
+        self.trader = trader;
+        self.volume_sol = self
+            .volume_sol
+            .checked_add(sol_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// A wallet's position in a token, built from its trades on the curve. Tokens obtained or sold
+/// outside the curve are not tracked, so sells only draw down the tokens bought here.
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub token: Pubkey,
+    pub tokens_held: u64,
+    /// Sol paid for the tokens still held, fees included
+    pub cost_basis_sol: u64,
+    /// Sol paid on all buys, fees included
+    pub total_sol_in: u64,
+    /// Sol received on all sells, after fees
+    pub total_sol_out: u64,
+    /// Lamports paid per whole token held
+    pub avg_entry_price: u64,
+}
+
+impl Position {
+    pub fn record_buy(
+        &mut self,
+        owner: Pubkey,
+        token: Pubkey,
+        sol_amount: u64,
+        token_amount: u64,
+    ) -> Result<()> {
+        self.owner = owner;
+        self.token = token;
+        self.tokens_held = self
+            .tokens_held
+            .checked_add(token_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.cost_basis_sol = self
+            .cost_basis_sol
+            .checked_add(sol_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.total_sol_in = self
+            .total_sol_in
+            .checked_add(sol_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        self.update_avg_entry_price()
+    }
+
+    pub fn record_sell(
+        &mut self,
+        owner: Pubkey,
+        token: Pubkey,
+        token_amount: u64,
+        sol_amount: u64,
+    ) -> Result<()> {
+        self.owner = owner;
+        self.token = token;
+        self.total_sol_out = self
+            .total_sol_out
+            .checked_add(sol_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        // The cost basis is drawn down in proportion to the tokens sold
+        let tokens_sold = token_amount.min(self.tokens_held);
+        if tokens_sold > 0 {
+            let cost_sold = utils::mul_div_floor(
+                &(self.cost_basis_sol as u128),
+                &(tokens_sold as u128),
+                &(self.tokens_held as u128),
+            )?;
+            self.cost_basis_sol -= cost_sold;
+            self.tokens_held -= tokens_sold;
+        }
+
+        self.update_avg_entry_price()
+    }
+
+    fn update_avg_entry_price(&mut self) -> Result<()> {
+        self.avg_entry_price = if self.tokens_held == 0 {
+            0
+        } else {
+            utils::mul_div_floor(
+                &(self.cost_basis_sol as u128),
+                &10u128.pow(constants::general::DECIMALS as u32),
+                &(self.tokens_held as u128),
+            )?
+        };
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vesting {
+    pub token: Pubkey,
+    pub creator: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+impl Vesting {
+    pub fn initialize(
+        &mut self,
+        token: Pubkey,
+        creator: Pubkey,
+        total_amount: u64,
+        creator_vesting: &CreatorVestingParams,
+    ) -> Result<()> {
+        require!(
+            creator_vesting.cliff_duration >= 0
+                && creator_vesting.vesting_duration > 0
+                && creator_vesting.cliff_duration <= creator_vesting.vesting_duration,
+            errors::CustomErrors::InvalidVestingSchedule
+        );
+
+        let start_time = Clock::get()?.unix_timestamp;
+
+        self.token = token;
+        self.creator = creator;
+        self.total_amount = total_amount;
+        self.claimed_amount = 0;
+        self.start_time = start_time;
+        self.cliff_time = start_time
+            .checked_add(creator_vesting.cliff_duration)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.end_time = start_time
+            .checked_add(creator_vesting.vesting_duration)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Amount vested at `now`: nothing before the cliff, then released linearly from the start
+    /// until everything is vested at the end.
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_time {
+            return Ok(0);
+        }
+        if now >= self.end_time {
+            return Ok(self.total_amount);
+        }
+
+        utils::mul_div_floor(
+            &(self.total_amount as u128),
+            &((now - self.start_time) as u128),
+            &((self.end_time - self.start_time) as u128),
+        )
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CurvePreset {
+    #[max_len(32)]
+    pub name: String,
+    pub config: CurveConfig,
+}
+
+// Types
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveType {
+    ConstantProduct,
+    Linear,
+    Exponential,
+}
+
+/// Whether creators can change their token's metadata after launch.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataPolicy {
+    Immutable,
+    /// The creator can update the metadata until the platform's update window closes or the token
+    /// graduates, after which it is locked.
+    CreatorUpdatable,
+}
+
+/// Outcome of a buy, returned by `quote_buy` and applied as is by `buy_tokens`.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct BuyQuote {
+    pub sol_amount: u64,
+    pub fee_in_bps: u64,
+    pub fee_amount: u64,
+    pub sol_amount_after_fee: u64,
+    pub token_amount: u64,
+    pub price_impact_bps: u64,
+    pub sol_reserve_after: u64,
+    pub token_reserve_after: u64,
+    pub real_token_reserve_after: u64,
+}
+
+/// Outcome of a sell, returned by `quote_sell` and applied as is by `sell_tokens`.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct SellQuote {
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub fee_in_bps: u64,
+    pub fee_amount: u64,
+    pub sol_amount_after_fee: u64,
+    pub price_impact_bps: u64,
+    pub sol_reserve_after: u64,
+    pub token_reserve_after: u64,
+    pub real_token_reserve_after: u64,
+}
+
+/// Full curve configuration a token is created with, either from the platform defaults or a preset.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone)]
+pub struct CurveConfig {
+    pub curve_type: CurveType,
+    pub total_supply: u64,
+    pub virtual_sol: u64,
+    pub virtual_token_reserve: u64,
+    pub real_token_reserve: u64,
+    pub target_pool_balance: u64,
+}
+
+impl CurveConfig {
+    pub fn has_valid_reserves(&self) -> bool {
+        self.real_token_reserve <= self.virtual_token_reserve
+            && self.real_token_reserve <= self.total_supply
+    }
+}
+
+/// Ranges within which creators may override the platform's curve parameters.
+/// Overrides are disabled while a range is left at zero, and zero values are always rejected.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Default)]
+pub struct CurveBounds {
+    pub min_total_supply: u64,
+    pub max_total_supply: u64,
+    pub min_virtual_sol: u64,
+    pub max_virtual_sol: u64,
+    pub min_target_pool_balance: u64,
+    pub max_target_pool_balance: u64,
+}
+
+impl CurveBounds {
+    pub fn is_valid(&self) -> bool {
+        self.min_total_supply <= self.max_total_supply
+            && self.min_virtual_sol <= self.max_virtual_sol
+            && self.min_target_pool_balance <= self.max_target_pool_balance
+    }
+}
+
+/// Anti-sniper protection applied to buys during the first `duration` seconds of a token.
+/// Disabled while `duration` is zero.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Default)]
+pub struct LaunchWindow {
+    pub duration: i64,
+    /// Sol (fee included) each wallet may spend on the token during the window
+    pub max_sol_per_wallet: u64,
+    /// Fee at creation, decaying to the platform fee by the end of the window. Ignored when lower
+    /// than the platform fee.
+    pub start_fee_in_bps: u64,
+}
+
+impl LaunchWindow {
+    pub fn is_valid(&self) -> bool {
+        self.duration >= 0
+            && (self.duration == 0 || self.max_sol_per_wallet > 0)
+            && self.start_fee_in_bps <= constants::general::MAX_LAUNCH_FEE_IN_BPS
+    }
+}
+
+/// Extra fee charged on trades that move the price by more than `threshold_bps`, adding
+/// `slope_bps` of the impact beyond the threshold to the fee. Disabled while `slope_bps` is zero.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Default)]
+pub struct PriceImpactFee {
+    pub threshold_bps: u64,
+    pub slope_bps: u64,
+}
+
+impl PriceImpactFee {
+    pub fn is_valid(&self) -> bool {
+        self.threshold_bps <= constants::general::BPS as u64
+            && self.slope_bps <= constants::general::BPS as u64
+    }
+}
+
+/// Running totals of the trades on the curve. Sol volumes are gross, fees included. Buyers are
+/// counted once per token, so the platform-wide count is of (wallet, token) pairs.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Default)]
+pub struct TradeStats {
+    pub total_fees: u64,
+    pub buy_volume_sol: u64,
+    pub buy_volume_tokens: u64,
+    pub sell_volume_sol: u64,
+    pub sell_volume_tokens: u64,
+    pub trade_count: u64,
+    pub unique_buyers: u64,
+}
+
+impl TradeStats {
+    pub fn record_buy(&mut self, quote: &BuyQuote, new_buyer: bool) -> Result<()> {
+        self.total_fees = self
+            .total_fees
+            .checked_add(quote.fee_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.buy_volume_sol = self
+            .buy_volume_sol
+            .checked_add(quote.sol_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.buy_volume_tokens = self
+            .buy_volume_tokens
+            .checked_add(quote.token_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.trade_count = self
+            .trade_count
+            .checked_add(1)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        if new_buyer {
+            self.unique_buyers = self
+                .unique_buyers
+                .checked_add(1)
+                .ok_or(errors::CustomErrors::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn record_sell(&mut self, quote: &SellQuote) -> Result<()> {
+        self.total_fees = self
+            .total_fees
+            .checked_add(quote.fee_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.sell_volume_sol = self
+            .sell_volume_sol
+            .checked_add(quote.sol_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.sell_volume_tokens = self
+            .sell_volume_tokens
+            .checked_add(quote.token_amount)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+        self.trade_count = self
+            .trade_count
+            .checked_add(1)
+            .ok_or(errors::CustomErrors::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// Fee discount for traders whose cumulative volume reached `min_volume_sol`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone)]
+pub struct FeeTier {
+    pub min_volume_sol: u64,
+    pub discount_bps: u64,
+}
+
+impl FeeTier {
+    /// Tiers must be sorted by strictly increasing volume and never lower the discount.
+    pub fn are_valid(tiers: &[FeeTier]) -> bool {
+        tiers.len() <= constants::general::MAX_FEE_TIERS
+            && tiers
+                .iter()
+                .all(|tier| tier.discount_bps <= constants::general::BPS as u64)
+            && tiers.windows(2).all(|pair| {
+                pair[0].min_volume_sol < pair[1].min_volume_sol
+                    && pair[0].discount_bps <= pair[1].discount_bps
+            })
+    }
+}
+
+/// Fee discount for traders holding at least `min_balance` of the platform token.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone)]
+pub struct HolderDiscountTier {
+    pub min_balance: u64,
+    pub discount_bps: u64,
+}
+
+impl HolderDiscountTier {
+    /// Tiers must be sorted by strictly increasing balance and never lower the discount.
+    pub fn are_valid(tiers: &[HolderDiscountTier]) -> bool {
+        tiers.len() <= constants::general::MAX_FEE_TIERS
+            && tiers
+                .iter()
+                .all(|tier| tier.discount_bps <= constants::general::BPS as u64)
+            && tiers.windows(2).all(|pair| {
+                pair[0].min_balance < pair[1].min_balance
+                    && pair[0].discount_bps <= pair[1].discount_bps
+            })
+    }
 }
 
 // Params
@@ -596,10 +2820,31 @@ pub struct TokenInfo {
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
 pub struct PlatformInitParams {
     pub owner: Pubkey,
-    pub fee_in_bps: u64,
+    pub buy_fee_bps: u64,
+    pub sell_fee_bps: u64,
     pub total_supply: u64,
     pub virtual_sol: u64,
+    pub virtual_token_reserve: u64,
+    pub real_token_reserve: u64,
     pub target_pool_balance: u64,
+    pub curve_type: CurveType,
+    pub curve_bounds: CurveBounds,
+    pub min_buy_sol_amount: u64,
+    pub min_sell_token_amount: u64,
+    pub metadata_policy: MetadataPolicy,
+    pub metadata_update_window: i64,
+    pub launch_window: LaunchWindow,
+    pub max_holding_bps: u64,
+    pub price_impact_fee: PriceImpactFee,
+    pub fee_tiers: Vec<FeeTier>,
+    pub platform_token_mint: Option<Pubkey>,
+    pub holder_discount_tiers: Vec<HolderDiscountTier>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct CurvePresetParams {
+    pub name: String,
+    pub config: CurveConfig,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
@@ -607,4 +2852,29 @@ pub struct CreateTokenParams {
     pub name: String,
     pub symbol: String,
     pub uri: String,
+    pub curve_type: Option<CurveType>,
+    pub total_supply: Option<u64>,
+    pub virtual_sol: Option<u64>,
+    pub target_pool_balance: Option<u64>,
+    /// Sol (fee included) the creator spends on the first buy, in the same transaction as the launch
+    pub initial_buy_sol: Option<u64>,
+    pub min_token_out: u64,
+    pub creator_vesting: Option<CreatorVestingParams>,
+    /// Tightens the platform's holding cap for this token
+    pub max_holding_bps: Option<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct CreatorVestingParams {
+    /// Share of the total supply set aside for the creator, capped by the platform
+    pub allocation_bps: u64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct UpdateTokenMetadataParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
 }
@@ -1,17 +1,862 @@
-use crate::constants::general;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::{token_2022::spl_token_2022, token_interface::TransferChecked};
 
-pub fn get_amount_out(amount_in: &u128, reserve_in: &u128, reserve_out: &u128) -> u64 {
-    (amount_in * reserve_out / (amount_in + reserve_in)) as u64
+use crate::constants::{general, metadata};
+use crate::errors::CustomErrors;
+use crate::{CurveType, PriceImpactFee};
+
+/// Snapshot of a token's curve state, used by the per-curve pricing functions.
+///
+/// `sol_reserve` includes `virtual_sol`, and `initial_token_reserve` is the token reserve the curve
+/// started with, so `initial_token_reserve - token_reserve` is the amount sold so far.
+pub struct CurveReserves {
+    pub sol_reserve: u128,
+    pub token_reserve: u128,
+    pub virtual_sol: u128,
+    pub initial_token_reserve: u128,
+}
+
+// Rounding
+//
+// Every amount leaving the curve (tokens bought, sol from sells) is rounded down while every amount
+// owed to it (fees, the sol the curve must keep after a sell) is rounded up, so that rounding errors
+// always accrue to the pool and repeated tiny trades cannot leak value out of it.
+
+/// Constant product output, rounded down.
+pub fn get_amount_out(amount_in: &u128, reserve_in: &u128, reserve_out: &u128) -> Result<u64> {
+    to_u64(div(
+        mul(*amount_in, *reserve_out)?,
+        add(*amount_in, *reserve_in)?,
+    )?)
+}
+
+pub fn get_amount_using_spot_price(
+    amount_in: &u128,
+    reserve_in: &u128,
+    reserve_out: &u128,
+) -> Result<u64> {
+    to_u64(div(mul(*amount_in, *reserve_out)?, *reserve_in)?)
+}
+
+pub fn mul_div_floor(amount: &u128, numerator: &u128, denominator: &u128) -> Result<u64> {
+    to_u64(div(mul(*amount, *numerator)?, *denominator)?)
+}
+
+pub fn mul_div_ceil(amount: &u128, numerator: &u128, denominator: &u128) -> Result<u64> {
+    to_u64(div_ceil(mul(*amount, *numerator)?, *denominator)?)
+}
+
+/// Fee in bps for a trade with the given price impact: the base fee plus the schedule's share of
+/// the impact beyond its threshold, rounded up. The extra fee stops at `MAX_ALLOWED_FEE_IN_BPS`
+/// but never lowers a base fee that is already above it.
+pub fn calculate_impact_fee_in_bps(
+    base_fee_in_bps: &u64,
+    price_impact_bps: &u64,
+    schedule: &PriceImpactFee,
+) -> Result<u64> {
+    if schedule.slope_bps == 0 || *price_impact_bps <= schedule.threshold_bps {
+        return Ok(*base_fee_in_bps);
+    }
+
+    let extra_fee_in_bps = mul_div_ceil(
+        &((price_impact_bps - schedule.threshold_bps) as u128),
+        &(schedule.slope_bps as u128),
+        &(general::BPS as u128),
+    )?;
+
+    Ok(base_fee_in_bps
+        .saturating_add(extra_fee_in_bps)
+        .min(general::MAX_ALLOWED_FEE_IN_BPS.max(*base_fee_in_bps)))
+}
+
+/// Fee in bps after taking `discount_bps` of it off. The discount is rounded down.
+pub fn apply_fee_discount(fee_in_bps: &u64, discount_bps: &u64) -> Result<u64> {
+    let discount = mul_div_floor(
+        &(*fee_in_bps as u128),
+        &(*discount_bps as u128),
+        &(general::BPS as u128),
+    )?;
+
+    Ok(fee_in_bps.saturating_sub(discount))
+}
+
+/// Fee taken out of the sol received from a sell, rounded up.
+pub fn calculate_sell_fee(amount: &u128, fee_in_bps: &u128) -> Result<u64> {
+    mul_div_ceil(amount, fee_in_bps, &(general::BPS as u128))
+}
+
+/// Fee included in the sol sent for a buy, rounded up.
+pub fn calculate_buy_fee(amount: &u128, fee_in_bps: &u128) -> Result<u64> {
+    mul_div_ceil(amount, fee_in_bps, &add(general::BPS as u128, *fee_in_bps)?)
+}
+
+/// Returns the creator's `value` if it lies within `[min, max]`, or the platform `default` if unset.
+/// A range left at zero disables the override, and zero is never accepted.
+pub fn resolve_curve_param(value: Option<u64>, default: u64, min: u64, max: u64) -> Result<u64> {
+    match value {
+        Some(value) => {
+            require!(max > 0, CustomErrors::CurveOverrideDisabled);
+            require!(value > 0, CustomErrors::ZeroCurveParam);
+            require!(
+                min <= value && value <= max,
+                CustomErrors::CurveParamOutOfBounds
+            );
+            Ok(value)
+        }
+        None => Ok(default),
+    }
+}
+
+/// Checks token metadata up front, so that creation fails with a clear error rather than an opaque
+/// one from the metadata program.
+pub fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(!name.is_empty(), CustomErrors::EmptyName);
+    require!(
+        name.len() <= metadata::MAX_NAME_LENGTH,
+        CustomErrors::NameTooLong
+    );
+    require!(!symbol.is_empty(), CustomErrors::EmptySymbol);
+    require!(
+        symbol.len() <= metadata::MAX_SYMBOL_LENGTH,
+        CustomErrors::SymbolTooLong
+    );
+    require!(!uri.is_empty(), CustomErrors::EmptyUri);
+    require!(
+        uri.len() <= metadata::MAX_URI_LENGTH,
+        CustomErrors::UriTooLong
+    );
+    require!(
+        [name, symbol, uri]
+            .iter()
+            .all(|value| !value.chars().any(char::is_control)),
+        CustomErrors::ControlCharacters
+    );
+    require!(
+        metadata::ALLOWED_URI_SCHEMES
+            .iter()
+            .any(|scheme| uri.starts_with(scheme)),
+        CustomErrors::InvalidUriScheme
+    );
+
+    Ok(())
+}
+
+/// Transfers tokens with `transfer_checked`, forwarding the context's remaining accounts so that
+/// mints with a transfer hook get the hook program and its extra accounts appended to the CPI.
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Funds `account` from `payer` so that it stays rent exempt once grown to `space` bytes, for
+/// accounts that the token program reallocates without paying for the extra space.
+pub fn top_up_rent<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let missing_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if missing_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+
+    Ok(())
+}
+
+// Curves
+//
+// With `V` the virtual sol, `T` the initial token reserve and `x` the amount of tokens sold, the
+// spot price (in lamports per token unit) of each curve is:
+// - constant product: `(V + C) / (T - x)` where `C` is the sol raised, i.e. `x * y = k`
+// - linear: `V / T * (1 + x / T)`, so the price doubles once `T` tokens are sold
+// - exponential: `V / T * e^(x / T)`
+//
+// Integrating the linear and exponential prices gives the sol raised `C(x)` for `x` tokens sold,
+// which is inverted on buys and evaluated on sells.
+
+/// Tokens received for `sol_in` (after fees) on the given curve, rounded down.
+pub fn get_buy_amount_out(
+    curve_type: &CurveType,
+    sol_in: &u128,
+    reserves: &CurveReserves,
+) -> Result<u64> {
+    match curve_type {
+        CurveType::ConstantProduct => {
+            get_amount_out(sol_in, &reserves.sol_reserve, &reserves.token_reserve)
+        }
+        CurveType::Linear => {
+            let t = reserves.initial_token_reserve;
+            let v = reserves.virtual_sol;
+            let sold = sub(t, reserves.token_reserve)?;
+            let raised = add(sub(reserves.sol_reserve, v)?, *sol_in)?;
+
+            // C(x) = V * x * (2T + x) / 2T^2  =>  x = sqrt(T^2 * (V + 2C) / V) - T
+            let scaled = div(mul(t, add(v, mul(2, raised)?)?)?, v)?;
+            let sold_after = sub(isqrt(mul(t, scaled)?), t)?;
+
+            to_u64(sold_after.saturating_sub(sold))
+        }
+        CurveType::Exponential => {
+            let t = reserves.initial_token_reserve;
+            let v = reserves.virtual_sol;
+            let sold = sub(t, reserves.token_reserve)?;
+
+            // C(x) = V * (e^(x / T) - 1)  =>  x = T * ln((V + C) / V)
+            let ratio = div(
+                mul(add(reserves.sol_reserve, *sol_in)?, general::PRECISION)?,
+                v,
+            )?;
+            let sold_after = div(mul(t, ln_fixed(ratio)?)?, general::PRECISION)?;
+
+            to_u64(sold_after.saturating_sub(sold))
+        }
+    }
+}
+
+/// Sol (before fees) received for selling `token_in` on the given curve, rounded down. Only
+/// tokens bought from the curve can be sold back into it.
+pub fn get_sell_amount_out(
+    curve_type: &CurveType,
+    token_in: &u128,
+    reserves: &CurveReserves,
+) -> Result<u64> {
+    require!(
+        *token_in <= sub(reserves.initial_token_reserve, reserves.token_reserve)?,
+        CustomErrors::SellExceedsCurve
+    );
+
+    match curve_type {
+        CurveType::ConstantProduct => {
+            get_amount_out(token_in, &reserves.token_reserve, &reserves.sol_reserve)
+        }
+        CurveType::Linear => {
+            let t = reserves.initial_token_reserve;
+            let v = reserves.virtual_sol;
+            let sold_after = sub(sub(t, reserves.token_reserve)?, *token_in)?;
+            let raised_after = div_ceil(
+                mul(
+                    div_ceil(mul(v, sold_after)?, t)?,
+                    add(mul(2, t)?, sold_after)?,
+                )?,
+                mul(2, t)?,
+            )?;
+
+            to_u64(sub(reserves.sol_reserve, v)?.saturating_sub(raised_after))
+        }
+        CurveType::Exponential => {
+            let t = reserves.initial_token_reserve;
+            let v = reserves.virtual_sol;
+            let sold_after = sub(sub(t, reserves.token_reserve)?, *token_in)?;
+            let exponent = div_ceil(mul(sold_after, general::PRECISION)?, t)?;
+            let sol_reserve_after = div_ceil(mul(v, exp_fixed(exponent)?)?, general::PRECISION)?;
+
+            to_u64(reserves.sol_reserve.saturating_sub(sol_reserve_after))
+        }
+    }
+}
+
+/// Tokens worth `sol_in` at the current spot price of the given curve, rounded down.
+pub fn get_spot_amount_out(
+    curve_type: &CurveType,
+    sol_in: &u128,
+    reserves: &CurveReserves,
+) -> Result<u64> {
+    match curve_type {
+        CurveType::ConstantProduct => {
+            get_amount_using_spot_price(sol_in, &reserves.sol_reserve, &reserves.token_reserve)
+        }
+        CurveType::Linear => {
+            let t = reserves.initial_token_reserve;
+            let sold = sub(t, reserves.token_reserve)?;
+
+            // sol_in / (V / T * (T + x) / T)
+            let amount = div(mul(*sol_in, t)?, reserves.virtual_sol)?;
+            to_u64(div(mul(amount, t)?, add(t, sold)?)?)
+        }
+        CurveType::Exponential => {
+            // V * e^(x / T) is the sol reserve, so the spot price is simply sol_reserve / T
+            get_amount_using_spot_price(
+                sol_in,
+                &reserves.sol_reserve,
+                &reserves.initial_token_reserve,
+            )
+        }
+    }
+}
+
+/// Spot price of the given curve in lamports per token unit, scaled by `general::PRECISION`.
+pub fn get_spot_price(curve_type: &CurveType, reserves: &CurveReserves) -> Result<u128> {
+    match curve_type {
+        CurveType::ConstantProduct => div(
+            mul(reserves.sol_reserve, general::PRECISION)?,
+            reserves.token_reserve,
+        ),
+        CurveType::Linear => {
+            let t = reserves.initial_token_reserve;
+            let sold = sub(t, reserves.token_reserve)?;
+            let start_price = div(mul(reserves.virtual_sol, general::PRECISION)?, t)?;
+
+            div(mul(start_price, add(t, sold)?)?, t)
+        }
+        CurveType::Exponential => div(
+            mul(reserves.sol_reserve, general::PRECISION)?,
+            reserves.initial_token_reserve,
+        ),
+    }
+}
+
+/// Relative move between two spot prices, in bps.
+pub fn get_price_impact_bps(price_before: &u128, price_after: &u128) -> Result<u64> {
+    to_u64(div(
+        mul(price_before.abs_diff(*price_after), general::BPS as u128)?,
+        *price_before,
+    )?)
+}
+
+// Fixed point helpers, scaled by `general::PRECISION`
+
+/// Integer square root, rounded down.
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = 1u128 << ((128 - value.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + value / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Natural logarithm of a fixed point `value >= 1`, rounded down.
+pub fn ln_fixed(value: u128) -> Result<u128> {
+    let one = general::PRECISION;
+
+    // ln(value) = k * ln(2) + ln(m) with m in [1, 2)
+    let mut k = 0u128;
+    let mut m = value;
+    while m >= 2 * one {
+        m /= 2;
+        k += 1;
+    }
+
+    // ln(m) = 2 * atanh(z) = 2 * (z + z^3 / 3 + z^5 / 5 + ...) with z = (m - 1) / (m + 1) < 1 / 3
+    let z = div(mul(sub(m, one)?, one)?, add(m, one)?)?;
+    let z_squared = div(mul(z, z)?, one)?;
+    let mut term = z;
+    let mut sum = 0u128;
+    let mut n = 1u128;
+    while term > 0 {
+        sum = add(sum, term / n)?;
+        term = div(mul(term, z_squared)?, one)?;
+        n += 2;
+    }
+
+    add(mul(k, general::LN_2)?, mul(2, sum)?)
+}
+
+/// Exponential of a fixed point `value` in `[0, 1]`, rounded up.
+pub fn exp_fixed(value: u128) -> Result<u128> {
+    let one = general::PRECISION;
+
+    // e^x = 1 + x + x^2 / 2! + x^3 / 3! + ...
+    let mut term = one;
+    let mut sum = one;
+    let mut n = 1u128;
+    while term > 0 {
+        term = div(mul(term, value)?, one)? / n;
+        sum = add(sum, term)?;
+        n += 1;
+    }
+
+    // Each truncated term is off by less than 2 units (its own rounding plus the carried error of
+    // the previous term, which shrinks as `value / n <= 1`), and so is the dropped tail
+    add(sum, mul(2, n)?)
 }
 
-pub fn get_amount_using_spot_price(amount_in: &u128, reserve_in: &u128, reserve_out: &u128) -> u64 {
-    ((amount_in * reserve_out) / reserve_in) as u64
+// Checked arithmetic
+
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| CustomErrors::MathOverflow.into())
 }
 
-pub fn calculate_sell_fee(amount: &u128, fee_in_bps: &u128) -> u64 {
-    ((amount * fee_in_bps) / general::BPS as u128) as u64
+fn add(a: u128, b: u128) -> Result<u128> {
+    Ok(a.checked_add(b).ok_or(CustomErrors::MathOverflow)?)
 }
 
-pub fn calculate_buy_fee(amount: &u128, fee_in_bps: &u128) -> u64 {
-    (amount * fee_in_bps / (general::BPS as u128 + fee_in_bps)) as u64
+fn sub(a: u128, b: u128) -> Result<u128> {
+    Ok(a.checked_sub(b).ok_or(CustomErrors::MathUnderflow)?)
+}
+
+fn mul(a: u128, b: u128) -> Result<u128> {
+    Ok(a.checked_mul(b).ok_or(CustomErrors::MathOverflow)?)
+}
+
+fn div(a: u128, b: u128) -> Result<u128> {
+    Ok(a.checked_div(b).ok_or(CustomErrors::DivisionByZero)?)
+}
+
+fn div_ceil(a: u128, b: u128) -> Result<u128> {
+    require!(b > 0, CustomErrors::DivisionByZero);
+    Ok(a.div_ceil(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[derive(Debug, Clone)]
+    enum Trade {
+        Buy(u64),
+        // Share of the trader's holdings to sell, in bps
+        Sell(u16),
+    }
+
+    /// Replays trades the way `buy_tokens`/`sell_tokens` apply them, dropping the ones they would
+    /// reject, while keeping track of what the trader paid and received.
+    struct Simulation {
+        curve_type: CurveType,
+        reserves: CurveReserves,
+        real_token_reserve: u128,
+        target_pool_balance: u128,
+        fee_in_bps: u128,
+        holdings: u128,
+        sol_paid: u128,
+        sol_received: u128,
+    }
+
+    impl Simulation {
+        fn buy(&mut self, sol_amount: u64) {
+            let Ok(fee) = calculate_buy_fee(&(sol_amount as u128), &self.fee_in_bps) else {
+                return;
+            };
+            let sol_in = sol_amount as u128 - fee as u128;
+            let Ok(token_out) = get_buy_amount_out(&self.curve_type, &sol_in, &self.reserves)
+            else {
+                return;
+            };
+            let token_out = token_out as u128;
+
+            if token_out == 0
+                || token_out > self.real_token_reserve
+                || self.reserves.sol_reserve + sol_in > self.target_pool_balance
+            {
+                return;
+            }
+
+            self.reserves.sol_reserve += sol_in;
+            self.reserves.token_reserve -= token_out;
+            self.real_token_reserve -= token_out;
+            self.holdings += token_out;
+            self.sol_paid += sol_amount as u128;
+        }
+
+        fn sell(&mut self, token_amount: u128) {
+            if token_amount == 0 || token_amount > self.holdings {
+                return;
+            }
+
+            let Ok(sol_out) = get_sell_amount_out(&self.curve_type, &token_amount, &self.reserves)
+            else {
+                return;
+            };
+            let sol_out = sol_out as u128;
+            let Ok(fee) = calculate_sell_fee(&sol_out, &self.fee_in_bps) else {
+                return;
+            };
+
+            if sol_out <= fee as u128 {
+                return;
+            }
+
+            self.reserves.sol_reserve -= sol_out;
+            self.reserves.token_reserve += token_amount;
+            self.real_token_reserve += token_amount;
+            self.holdings -= token_amount;
+            self.sol_received += sol_out - fee as u128;
+        }
+    }
+
+    fn curve_type() -> impl Strategy<Value = CurveType> {
+        prop_oneof![
+            Just(CurveType::ConstantProduct),
+            Just(CurveType::Linear),
+            Just(CurveType::Exponential),
+        ]
+    }
+
+    fn trade() -> impl Strategy<Value = Trade> {
+        prop_oneof![
+            (1u64..=10_000_000_000).prop_map(Trade::Buy),
+            (1u64..=1_000_000).prop_map(Trade::Buy),
+            (1u16..=10_000).prop_map(Trade::Sell),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn trades_never_extract_more_sol_than_deposited(
+            curve_type in curve_type(),
+            virtual_sol in 1_000_000_000u128..=100_000_000_000,
+            virtual_token_reserve in 1_000_000_000u128..=1_000_000_000_000_000_000,
+            real_token_share_bps in 1u128..=10_000,
+            fee_in_bps in 0u128..=500,
+            trades in prop::collection::vec(trade(), 1..40),
+        ) {
+            let mut simulation = Simulation {
+                curve_type,
+                reserves: CurveReserves {
+                    sol_reserve: virtual_sol,
+                    token_reserve: virtual_token_reserve,
+                    virtual_sol,
+                    initial_token_reserve: virtual_token_reserve,
+                },
+                real_token_reserve: virtual_token_reserve * real_token_share_bps / 10_000,
+                target_pool_balance: virtual_sol * 3,
+                fee_in_bps,
+                holdings: 0,
+                sol_paid: 0,
+                sol_received: 0,
+            };
+
+            for trade in trades {
+                match trade {
+                    Trade::Buy(sol_amount) => simulation.buy(sol_amount),
+                    Trade::Sell(share_bps) => {
+                        simulation.sell(simulation.holdings * share_bps as u128 / 10_000)
+                    }
+                }
+
+                // The sol custodied by the curve always covers what was raised on it
+                prop_assert!(simulation.reserves.sol_reserve >= virtual_sol);
+                prop_assert!(simulation.sol_received <= simulation.sol_paid);
+            }
+
+            simulation.sell(simulation.holdings);
+
+            prop_assert!(simulation.sol_received <= simulation.sol_paid);
+        }
+
+        #[test]
+        fn buy_then_sell_round_trip_never_profits(
+            curve_type in curve_type(),
+            virtual_sol in 1_000_000_000u128..=100_000_000_000,
+            virtual_token_reserve in 1_000_000_000u128..=1_000_000_000_000_000_000,
+            sold_share_bps in 0u128..5_000,
+            sol_in in 1u128..=10_000_000_000,
+        ) {
+            // Start from an arbitrary point on the curve, with the sol it holds at that point
+            let mut reserves = CurveReserves {
+                sol_reserve: virtual_sol,
+                token_reserve: virtual_token_reserve,
+                virtual_sol,
+                initial_token_reserve: virtual_token_reserve,
+            };
+            let opening_sol = virtual_sol * sold_share_bps / 10_000;
+            let opened = get_buy_amount_out(&curve_type, &opening_sol, &reserves).unwrap() as u128;
+            prop_assume!(opened < reserves.token_reserve);
+            reserves.sol_reserve += opening_sol;
+            reserves.token_reserve -= opened;
+
+            let token_out = get_buy_amount_out(&curve_type, &sol_in, &reserves).unwrap() as u128;
+            // The real token reserve stops the linear curve before it sells out
+            prop_assume!(token_out < reserves.token_reserve);
+            reserves.sol_reserve += sol_in;
+            reserves.token_reserve -= token_out;
+
+            let sol_out = get_sell_amount_out(&curve_type, &token_out, &reserves).unwrap() as u128;
+
+            prop_assert!(sol_out <= sol_in);
+        }
+    }
+
+    /// A curve that started with 1 SOL of virtual sol and 1e9 tokens, after `sold` tokens were
+    /// bought for `raised` lamports.
+    fn reserves_after(sold: u128, raised: u128) -> CurveReserves {
+        CurveReserves {
+            sol_reserve: 1_000_000_000 + raised,
+            token_reserve: 1_000_000_000 - sold,
+            virtual_sol: 1_000_000_000,
+            initial_token_reserve: 1_000_000_000,
+        }
+    }
+
+    #[test]
+    fn linear_curve_follows_its_closed_form() {
+        let curve_type = CurveType::Linear;
+        let one = general::PRECISION;
+
+        // The price starts at V / T and doubles once T tokens are sold
+        assert_eq!(
+            get_spot_price(&curve_type, &reserves_after(0, 0)).unwrap(),
+            one
+        );
+        assert_eq!(
+            get_spot_price(&curve_type, &reserves_after(500_000_000, 625_000_000)).unwrap(),
+            3 * one / 2
+        );
+        assert_eq!(
+            get_spot_price(&curve_type, &reserves_after(1_000_000_000, 1_500_000_000)).unwrap(),
+            2 * one
+        );
+
+        // C(x) = V * x * (2T + x) / 2T^2, so T / 2 tokens cost 0.625 SOL and T tokens 1.5 SOL
+        assert_eq!(
+            get_buy_amount_out(&curve_type, &625_000_000, &reserves_after(0, 0)).unwrap(),
+            500_000_000
+        );
+        assert_eq!(
+            get_buy_amount_out(&curve_type, &1_500_000_000, &reserves_after(0, 0)).unwrap(),
+            1_000_000_000
+        );
+        assert_eq!(
+            get_buy_amount_out(
+                &curve_type,
+                &875_000_000,
+                &reserves_after(500_000_000, 625_000_000)
+            )
+            .unwrap(),
+            500_000_000
+        );
+        assert_eq!(
+            get_sell_amount_out(
+                &curve_type,
+                &500_000_000,
+                &reserves_after(500_000_000, 625_000_000)
+            )
+            .unwrap(),
+            625_000_000
+        );
+        assert_eq!(
+            get_sell_amount_out(
+                &curve_type,
+                &500_000_000,
+                &reserves_after(1_000_000_000, 1_500_000_000)
+            )
+            .unwrap(),
+            875_000_000
+        );
+
+        // 3 SOL at a price of 1.5 lamports per token
+        assert_eq!(
+            get_spot_amount_out(
+                &curve_type,
+                &3_000_000_000,
+                &reserves_after(500_000_000, 625_000_000)
+            )
+            .unwrap(),
+            2_000_000_000
+        );
+    }
+
+    #[test]
+    fn exponential_curve_follows_its_closed_form() {
+        let curve_type = CurveType::Exponential;
+        let one = general::PRECISION;
+        // C(T) = V * (e - 1), rounded up
+        let raised = 1_718_281_829;
+
+        // The price starts at V / T and grows by a factor e once T tokens are sold
+        assert_eq!(
+            get_spot_price(&curve_type, &reserves_after(0, 0)).unwrap(),
+            one
+        );
+        assert_eq!(
+            get_spot_price(&curve_type, &reserves_after(1_000_000_000, raised)).unwrap(),
+            2_718_281_829 * one / 1_000_000_000
+        );
+
+        assert_eq!(
+            get_buy_amount_out(&curve_type, &raised, &reserves_after(0, 0)).unwrap(),
+            1_000_000_000
+        );
+        assert_eq!(
+            get_sell_amount_out(
+                &curve_type,
+                &1_000_000_000,
+                &reserves_after(1_000_000_000, raised)
+            )
+            .unwrap(),
+            1_718_281_828
+        );
+        assert_eq!(
+            get_spot_amount_out(
+                &curve_type,
+                &2_718_281_829,
+                &reserves_after(1_000_000_000, raised)
+            )
+            .unwrap(),
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn sells_cannot_return_more_than_was_bought() {
+        for curve_type in [
+            CurveType::ConstantProduct,
+            CurveType::Linear,
+            CurveType::Exponential,
+        ] {
+            let reserves = reserves_after(500_000_000, 625_000_000);
+
+            assert!(get_sell_amount_out(&curve_type, &500_000_000, &reserves).is_ok());
+            assert_eq!(
+                get_sell_amount_out(&curve_type, &500_000_001, &reserves).unwrap_err(),
+                CustomErrors::SellExceedsCurve.into()
+            );
+            assert_eq!(
+                get_sell_amount_out(&curve_type, &1, &reserves_after(0, 0)).unwrap_err(),
+                CustomErrors::SellExceedsCurve.into()
+            );
+        }
+    }
+
+    #[test]
+    fn checked_math_fails_with_typed_errors() {
+        let error = |result: Result<u128>| result.unwrap_err();
+
+        assert_eq!(add(u128::MAX, 0).unwrap(), u128::MAX);
+        assert_eq!(error(add(u128::MAX, 1)), CustomErrors::MathOverflow.into());
+        assert_eq!(sub(1, 1).unwrap(), 0);
+        assert_eq!(error(sub(0, 1)), CustomErrors::MathUnderflow.into());
+        assert_eq!(error(mul(u128::MAX, 2)), CustomErrors::MathOverflow.into());
+        assert_eq!(div(7, 2).unwrap(), 3);
+        assert_eq!(error(div(1, 0)), CustomErrors::DivisionByZero.into());
+        assert_eq!(div_ceil(7, 2).unwrap(), 4);
+        assert_eq!(error(div_ceil(1, 0)), CustomErrors::DivisionByZero.into());
+    }
+
+    #[test]
+    fn u64_conversions_never_truncate() {
+        assert_eq!(to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+        assert_eq!(
+            to_u64(u64::MAX as u128 + 1).unwrap_err(),
+            CustomErrors::MathOverflow.into()
+        );
+        assert_eq!(
+            mul_div_floor(&(u64::MAX as u128), &2, &1).unwrap_err(),
+            CustomErrors::MathOverflow.into()
+        );
+        assert_eq!(
+            get_amount_using_spot_price(&(u64::MAX as u128), &1, &2).unwrap_err(),
+            CustomErrors::MathOverflow.into()
+        );
+    }
+
+    #[test]
+    fn fees_round_up() {
+        assert_eq!(calculate_sell_fee(&1, &100).unwrap(), 1);
+        assert_eq!(calculate_sell_fee(&10_000, &100).unwrap(), 100);
+        assert_eq!(calculate_buy_fee(&1, &100).unwrap(), 1);
+        assert_eq!(calculate_buy_fee(&10_100, &100).unwrap(), 100);
+        assert_eq!(calculate_buy_fee(&10_101, &100).unwrap(), 101);
+        assert_eq!(calculate_sell_fee(&1_000, &0).unwrap(), 0);
+    }
+
+    #[test]
+    fn curve_param_overrides_reject_zero() {
+        assert_eq!(resolve_curve_param(None, 7, 0, 0).unwrap(), 7);
+        assert_eq!(resolve_curve_param(Some(5), 7, 1, 10).unwrap(), 5);
+        assert!(resolve_curve_param(Some(0), 7, 0, 0).is_err());
+        assert!(resolve_curve_param(Some(0), 7, 0, 10).is_err());
+        assert!(resolve_curve_param(Some(11), 7, 1, 10).is_err());
+    }
+
+    #[test]
+    fn metadata_validation_rejects_bad_fields() {
+        assert!(validate_metadata("Token", "T", "https://www.example.com").is_ok());
+        assert!(validate_metadata("Token", "T", "ipfs://bafy").is_ok());
+        assert!(validate_metadata("", "T", "ar://tx").is_err());
+        assert!(validate_metadata(&"a".repeat(33), "T", "ar://tx").is_err());
+        assert!(validate_metadata("Token", "TOOLONGSYMB", "ar://tx").is_err());
+        assert!(validate_metadata("To\nken", "T", "ar://tx").is_err());
+        assert!(validate_metadata("Token", "T", "http://www.example.com").is_err());
+        assert!(validate_metadata("Token", "T", &format!("ar://{}", "a".repeat(200))).is_err());
+    }
+
+    #[test]
+    fn impact_fee_grows_with_price_impact_and_is_capped() {
+        let schedule = PriceImpactFee {
+            threshold_bps: 100,
+            slope_bps: 1_000,
+        };
+
+        assert_eq!(
+            calculate_impact_fee_in_bps(&100, &50, &schedule).unwrap(),
+            100
+        );
+        assert_eq!(
+            calculate_impact_fee_in_bps(&100, &1_100, &schedule).unwrap(),
+            200
+        );
+        assert_eq!(
+            calculate_impact_fee_in_bps(&100, &1_101, &schedule).unwrap(),
+            201
+        );
+        assert_eq!(
+            calculate_impact_fee_in_bps(&100, &10_000, &schedule).unwrap(),
+            general::MAX_ALLOWED_FEE_IN_BPS
+        );
+        assert_eq!(
+            calculate_impact_fee_in_bps(&1_000, &10_000, &schedule).unwrap(),
+            1_000
+        );
+        assert_eq!(
+            calculate_impact_fee_in_bps(&100, &10_000, &PriceImpactFee::default()).unwrap(),
+            100
+        );
+    }
+
+    #[test]
+    fn fee_discount_rounds_in_favor_of_the_platform() {
+        assert_eq!(apply_fee_discount(&100, &0).unwrap(), 100);
+        assert_eq!(apply_fee_discount(&100, &2_500).unwrap(), 75);
+        assert_eq!(apply_fee_discount(&99, &5_000).unwrap(), 50);
+        assert_eq!(apply_fee_discount(&100, &10_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn fixed_point_helpers_bracket_the_exact_values() {
+        let one = general::PRECISION;
+
+        assert!(ln_fixed(2 * one).unwrap() <= general::LN_2);
+        assert!(ln_fixed(2 * one).unwrap() >= general::LN_2 - 100);
+        assert!(exp_fixed(one).unwrap() >= 2_718_281_828_459_045_235);
+        assert!(exp_fixed(one).unwrap() <= 2_718_281_828_459_045_235 + 100);
+        assert!(exp_fixed(0).unwrap() >= one);
+        assert!(exp_fixed(0).unwrap() <= one + 100);
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(100), 10);
+    }
 }
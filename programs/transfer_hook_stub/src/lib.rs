@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("9ddXModmjU2viTyi6NJcvRquGRcUP6RMgu4xGnbwaCmm");
+
+const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+const TRANSFER_COUNTER_SEED: &[u8] = b"transfer_counter";
+
+/// Minimal transfer hook that counts the transfers of a mint, used to test trading hooked
+/// Token-2022 launches.
+#[program]
+pub mod transfer_hook_stub {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        // The counter is derived from the mint, which is the second account of every transfer
+        let extra_account_metas = [ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: TRANSFER_COUNTER_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?];
+
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas,
+        )?;
+
+        Ok(())
+    }
+
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        ctx.accounts.transfer_counter.transfers += 1;
+
+        Ok(())
+    }
+}
+
+// Contexts
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Validation account read by the token program, initialized by this instruction
+    #[account(init, payer=payer, seeds=[EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump, space=ExtraAccountMetaList::size_of(1)?)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    /// CHECK: Only used to derive the hook accounts
+    pub mint: UncheckedAccount<'info>,
+    #[account(init, payer=payer, seeds=[TRANSFER_COUNTER_SEED, mint.key().as_ref()], bump, space=8 + TransferCounter::INIT_SPACE)]
+    pub transfer_counter: Account<'info, TransferCounter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    /// CHECK: Source token account, validated by the token program
+    pub source_token: UncheckedAccount<'info>,
+    /// CHECK: Mint being transferred, validated by the token program
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: Destination token account, validated by the token program
+    pub destination_token: UncheckedAccount<'info>,
+    /// CHECK: Owner or delegate of the source account
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: Validation account of this hook
+    #[account(seeds=[EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(mut, seeds=[TRANSFER_COUNTER_SEED, mint.key().as_ref()], bump)]
+    pub transfer_counter: Account<'info, TransferCounter>,
+}
+
+// Accounts
+
+#[account]
+#[derive(InitSpace)]
+pub struct TransferCounter {
+    pub transfers: u64,
+}
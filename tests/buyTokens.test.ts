@@ -1,6 +1,12 @@
 import * as anchor from "@coral-xyz/anchor";
 import { assert } from "chai";
-import { program, tokenDetails, keypairs, seedStrings } from "./utils/constants";
+import {
+    program,
+    platformParams,
+    tokenDetails,
+    keypairs,
+    deriveTokenKeypairs,
+} from "./utils/constants";
 import * as spl from "@solana/spl-token";
 
 describe("Solana pump fun", () => {
@@ -8,28 +14,19 @@ describe("Solana pump fun", () => {
     anchor.setProvider(provider);
 
     const owner = (provider.wallet as anchor.Wallet).payer;
-    const feeInBps = new anchor.BN(100); // 1%
-    const totalSupply = new anchor.BN(100e9);
-    const virtualSol = new anchor.BN(100e9);
-    const targetPoolBalance = new anchor.BN(150e9);
 
     before(async () => {
-        const platformParams = {
-            owner: owner.publicKey,
-            feeInBps,
-            totalSupply,
-            virtualSol,
-            targetPoolBalance,
-        };
-
         await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
 
         await program.methods
             .createToken(tokenDetails)
             .accounts({
+                mint: keypairs.mintKeypair,
                 metadata: keypairs.metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
             })
-            .signers([owner])
+            .signers([owner, keypairs.mintSigner])
             .rpc();
     });
 
@@ -49,6 +46,8 @@ describe("Solana pump fun", () => {
                 mint: keypairs.mintKeypair.toBase58(),
                 tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
                 userTokenAccount: buyerTokenAccount,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+                platformTokenAccount: null,
             })
             .signers([owner])
             .rpc();
@@ -56,4 +55,211 @@ describe("Solana pump fun", () => {
         const balance = await provider.connection.getTokenAccountBalance(buyerTokenAccount);
         assert(+balance.value.amount > 9e8);
     });
+
+    it("Cannot buy below the platform minimum", async () => {
+        const buyerTokenAccount = spl.getAssociatedTokenAddressSync(
+            keypairs.mintKeypair,
+            owner.publicKey
+        );
+
+        try {
+            await program.methods
+                .buyTokens(new anchor.BN(1))
+                .accounts({
+                    mint: keypairs.mintKeypair.toBase58(),
+                    tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                    userTokenAccount: buyerTokenAccount,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                    platformTokenAccount: null,
+                })
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the dust buy to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "TradeTooSmall");
+        }
+    });
+
+    it("Cannot buy past the token's holding cap before launch", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { metadataKeypair, tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+        await program.methods
+            .createToken({ ...tokenDetails, maxHoldingBps: new anchor.BN(100) }) // 1%
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+
+        const buyerTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            mintSigner.publicKey,
+            owner.publicKey
+        );
+
+        try {
+            await program.methods
+                .buyTokens(new anchor.BN(2e9))
+                .accounts({
+                    mint: mintSigner.publicKey.toBase58(),
+                    tokenInfo: tokenInfoKeypair.toBase58(),
+                    userTokenAccount: buyerTokenAccount,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                    platformTokenAccount: null,
+                })
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the buy to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "MaxHoldingExceeded");
+        }
+    });
+
+    it("Cannot loosen the platform's holding cap when creating a token", async () => {
+        await program.methods
+            .changeMaxHolding(new anchor.BN(200)) // 2%
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        for (const maxHoldingBps of [new anchor.BN(0), new anchor.BN(300)]) {
+            const mintSigner = anchor.web3.Keypair.generate();
+            const { metadataKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+            try {
+                await program.methods
+                    .createToken({ ...tokenDetails, maxHoldingBps })
+                    .accounts({
+                        mint: mintSigner.publicKey,
+                        metadata: metadataKeypair.toBase58(),
+                        curvePreset: null,
+                        platformTokenAccount: null,
+                    })
+                    .signers([owner, mintSigner])
+                    .rpc();
+                assert.fail("Expected the looser holding cap to be rejected");
+            } catch (err) {
+                assert.include((err as Error).message, "MaxHoldingLoosened");
+            }
+        }
+
+        await program.methods
+            .changeMaxHolding(new anchor.BN(0))
+            .accounts({})
+            .signers([owner])
+            .rpc();
+    });
+
+    it("Discounts the fee of platform token holders", async () => {
+        const solAmount = new anchor.BN(1e8);
+        const platformTokenMint = await spl.createMint(
+            provider.connection,
+            owner,
+            owner.publicKey,
+            null,
+            9
+        );
+        const platformTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            platformTokenMint,
+            owner.publicKey
+        );
+        await spl.mintTo(
+            provider.connection,
+            owner,
+            platformTokenMint,
+            platformTokenAccount,
+            owner,
+            1e9
+        );
+
+        await program.methods
+            .changePlatformToken(platformTokenMint, [
+                { minBalance: new anchor.BN(1e9), discountBps: new anchor.BN(5000) },
+            ])
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const quote = await program.methods
+            .quoteBuy(solAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                trader: owner.publicKey,
+                traderVolume: null,
+                platformTokenAccount,
+            })
+            .view();
+        assert.equal(quote.feeInBps.toNumber(), platformParams.buyFeeBps.toNumber() / 2);
+
+        const platformBefore = await program.account.platform.fetch(
+            keypairs.platformKeypair.toBase58()
+        );
+
+        await program.methods
+            .buyTokens(solAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                userTokenAccount: spl.getAssociatedTokenAddressSync(
+                    keypairs.mintKeypair,
+                    owner.publicKey
+                ),
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+                platformTokenAccount,
+            })
+            .signers([owner])
+            .rpc();
+
+        const platformAfter = await program.account.platform.fetch(
+            keypairs.platformKeypair.toBase58()
+        );
+        assert.equal(
+            platformAfter.accumulatedFees.sub(platformBefore.accumulatedFees).toNumber(),
+            quote.feeAmount.toNumber()
+        );
+
+        const userTokenAccount = spl.getAssociatedTokenAddressSync(
+            keypairs.mintKeypair,
+            owner.publicKey
+        );
+        const strangersPlatformTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            platformTokenMint,
+            anchor.web3.Keypair.generate().publicKey
+        );
+        for (const [invalidPlatformTokenAccount, expectedError] of [
+            // Someone else's platform token account
+            [strangersPlatformTokenAccount, "ConstraintTokenOwner"],
+            // A token account of another mint
+            [userTokenAccount, "InvalidPlatformTokenAccount"],
+        ] as const) {
+            try {
+                await program.methods
+                    .buyTokens(solAmount)
+                    .accounts({
+                        mint: keypairs.mintKeypair.toBase58(),
+                        tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                        userTokenAccount,
+                        tokenProgram: spl.TOKEN_PROGRAM_ID,
+                        platformTokenAccount: invalidPlatformTokenAccount,
+                    })
+                    .signers([owner])
+                    .rpc();
+                assert.fail("Expected the platform token account to be rejected");
+            } catch (err) {
+                assert.include((err as Error).message, expectedError);
+            }
+        }
+
+        await program.methods.changePlatformToken(null, []).accounts({}).signers([owner]).rpc();
+    });
 });
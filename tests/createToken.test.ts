@@ -1,36 +1,89 @@
 import * as anchor from "@coral-xyz/anchor";
 import { assert } from "chai";
-import { program, tokenDetails, keypairs } from "./utils/constants";
+import {
+    program,
+    platformParams,
+    tokenDetails,
+    keypairs,
+    deriveTokenKeypairs,
+    fetchMetaplexMetadata,
+} from "./utils/constants";
+import * as spl from "@solana/spl-token";
 
 describe("Solana pump fun", () => {
     const provider = anchor.AnchorProvider.env();
     anchor.setProvider(provider);
 
     const owner = (provider.wallet as anchor.Wallet).payer;
-    const feeInBps = new anchor.BN(100); // 1%
-    const totalSupply = new anchor.BN(100e9);
-    const virtualSol = new anchor.BN(100e9);
-    const targetPoolBalance = new anchor.BN(150e9);
 
     before(async () => {
-        const platformParams = {
-            owner: owner.publicKey,
-            feeInBps,
-            totalSupply,
-            virtualSol,
-            targetPoolBalance,
-        };
-
         await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
     });
 
+    it("Cannot override curve params outside platform bounds", async () => {
+        try {
+            await program.methods
+                .createToken({ ...tokenDetails, totalSupply: new anchor.BN(500e9) })
+                .accounts({
+                    mint: keypairs.mintKeypair,
+                    metadata: keypairs.metadataKeypair.toBase58(),
+                    curvePreset: null,
+                    platformTokenAccount: null,
+                })
+                .signers([owner, keypairs.mintSigner])
+                .rpc();
+            assert.fail("Expected the override to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "CurveParamOutOfBounds");
+        }
+    });
+
+    it("Cannot create a token with an unsupported URI scheme", async () => {
+        try {
+            await program.methods
+                .createToken({ ...tokenDetails, uri: "http://www.example.com" })
+                .accounts({
+                    mint: keypairs.mintKeypair,
+                    metadata: keypairs.metadataKeypair.toBase58(),
+                    curvePreset: null,
+                    platformTokenAccount: null,
+                })
+                .signers([owner, keypairs.mintSigner])
+                .rpc();
+            assert.fail("Expected the URI to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "InvalidUriScheme");
+        }
+    });
+
+    it("Cannot create a token with an overlong name", async () => {
+        try {
+            await program.methods
+                .createToken({ ...tokenDetails, name: "T".repeat(33) })
+                .accounts({
+                    mint: keypairs.mintKeypair,
+                    metadata: keypairs.metadataKeypair.toBase58(),
+                    curvePreset: null,
+                    platformTokenAccount: null,
+                })
+                .signers([owner, keypairs.mintSigner])
+                .rpc();
+            assert.fail("Expected the name to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "NameTooLong");
+        }
+    });
+
     it("Can create new token", async () => {
         await program.methods
             .createToken(tokenDetails)
             .accounts({
+                mint: keypairs.mintKeypair,
                 metadata: keypairs.metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
             })
-            .signers([owner])
+            .signers([owner, keypairs.mintSigner])
             .rpc();
 
         const tokenInfo = await program.account.tokenInfo.fetch(
@@ -38,11 +91,226 @@ describe("Solana pump fun", () => {
         );
 
         assert.equal(tokenInfo.token.toString(), keypairs.mintKeypair.toString());
-        assert.equal(tokenInfo.totalSupply.toNumber(), totalSupply.toNumber());
-        assert.equal(tokenInfo.virtualSol.toNumber(), virtualSol.toNumber());
-        assert.equal(tokenInfo.solReserve.toNumber(), virtualSol.toNumber());
-        assert.equal(tokenInfo.tokenReserve.toNumber(), totalSupply.toNumber());
-        assert.equal(tokenInfo.targetPoolBalance.toNumber(), targetPoolBalance.toNumber());
+        assert.equal(tokenInfo.totalSupply.toNumber(), platformParams.totalSupply.toNumber());
+        assert.equal(tokenInfo.virtualSol.toNumber(), platformParams.virtualSol.toNumber());
+        assert.equal(tokenInfo.solReserve.toNumber(), platformParams.virtualSol.toNumber());
+        assert.equal(
+            tokenInfo.virtualTokenReserve.toNumber(),
+            platformParams.virtualTokenReserve.toNumber()
+        );
+        assert.equal(
+            tokenInfo.tokenReserve.toNumber(),
+            platformParams.virtualTokenReserve.toNumber()
+        );
+        assert.equal(
+            tokenInfo.realTokenReserve.toNumber(),
+            platformParams.realTokenReserve.toNumber()
+        );
+        assert.equal(
+            tokenInfo.targetPoolBalance.toNumber(),
+            platformParams.targetPoolBalance.toNumber()
+        );
+        assert.deepEqual(tokenInfo.curveType, platformParams.curveType);
+    });
+
+    it("Revokes the mint authority once the supply is minted", async () => {
+        const mint = await spl.getMint(provider.connection, keypairs.mintKeypair);
+
+        assert.isNull(mint.mintAuthority);
+        assert.isNull(mint.freezeAuthority);
+        assert.equal(mint.supply.toString(), platformParams.totalSupply.toString());
+    });
+
+    it("Creator can update the token metadata before graduation", async () => {
+        const newMetadata = { name: "Renamed", symbol: "R", uri: "ipfs://renamed" };
+
+        await program.methods
+            .updateTokenMetadata(newMetadata)
+            .accounts({
+                mint: keypairs.mintKeypair,
+                metadata: keypairs.metadataKeypair,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+            })
+            .signers([owner])
+            .rpc();
+
+        const metadata = await fetchMetaplexMetadata(keypairs.metadataKeypair);
+
+        assert.equal(metadata.name, newMetadata.name);
+        assert.equal(metadata.symbol, newMetadata.symbol);
+        assert.equal(metadata.uri, newMetadata.uri);
+        assert.isTrue(metadata.isMutable);
+    });
+
+    it("Cannot lock the token metadata while the creator can still update it", async () => {
+        try {
+            await program.methods
+                .lockTokenMetadata()
+                .accounts({
+                    mint: keypairs.mintKeypair,
+                    metadata: keypairs.metadataKeypair,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                })
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the lock to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "MetadataStillUpdatable");
+        }
+    });
+
+    it("Can lock the token metadata once the update window closes", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { metadataKeypair, tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+        await program.methods
+            .changeMetadataPolicy({ creatorUpdatable: {} }, new anchor.BN(0))
+            .accounts({})
+            .signers([owner])
+            .rpc();
+        await program.methods
+            .createToken(tokenDetails)
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+        await program.methods
+            .changeMetadataPolicy(
+                platformParams.metadataPolicy,
+                platformParams.metadataUpdateWindow
+            )
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        // Let the zero-length update window pass
+        await new Promise((resolve) => setTimeout(resolve, 2000));
+
+        await program.methods
+            .lockTokenMetadata()
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: metadataKeypair,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+            })
+            .signers([owner])
+            .rpc();
+
+        const metadata = await fetchMetaplexMetadata(metadataKeypair);
+        const tokenInfo = await program.account.tokenInfo.fetch(tokenInfoKeypair.toBase58());
+
+        assert.isFalse(metadata.isMutable);
+        assert.equal(tokenInfo.metadataUpdateDeadline.toNumber(), 0);
+    });
+
+    it("Only the creator can update the token metadata", async () => {
+        const stranger = anchor.web3.Keypair.generate();
+        await provider.connection.confirmTransaction(
+            await provider.connection.requestAirdrop(stranger.publicKey, 1e9)
+        );
+
+        try {
+            await program.methods
+                .updateTokenMetadata({ name: "Stolen", symbol: "S", uri: "ar://stolen" })
+                .accounts({
+                    signer: stranger.publicKey,
+                    mint: keypairs.mintKeypair,
+                    metadata: keypairs.metadataKeypair,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                })
+                .signers([stranger])
+                .rpc();
+            assert.fail("Expected the update to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "NotCreator");
+        }
+    });
+
+    it("Can create another token with the same name", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { metadataKeypair, tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+        await program.methods
+            .createToken(tokenDetails)
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+
+        const tokenInfo = await program.account.tokenInfo.fetch(tokenInfoKeypair.toBase58());
+
+        assert.equal(tokenInfo.token.toString(), mintSigner.publicKey.toString());
+    });
+
+    it("Creator can buy in the same transaction as the launch", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { metadataKeypair, tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+        const creatorTokenAccount = spl.getAssociatedTokenAddressSync(
+            mintSigner.publicKey,
+            owner.publicKey
+        );
+
+        await program.methods
+            .createToken({
+                ...tokenDetails,
+                initialBuySol: new anchor.BN(1e9),
+                minTokenOut: new anchor.BN(9e8),
+            })
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: metadataKeypair.toBase58(),
+                curvePreset: null,
+                creatorTokenAccount,
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+
+        const tokenInfo = await program.account.tokenInfo.fetch(tokenInfoKeypair.toBase58());
+        const balance = await provider.connection.getTokenAccountBalance(creatorTokenAccount);
+
+        assert(+balance.value.amount >= 9e8);
+        assert.equal(
+            tokenInfo.realTokenReserve.toString(),
+            platformParams.realTokenReserve.sub(new anchor.BN(balance.value.amount)).toString()
+        );
+    });
+
+    it("Cannot launch with an initial buy below the minimum output", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { metadataKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+        try {
+            await program.methods
+                .createToken({
+                    ...tokenDetails,
+                    initialBuySol: new anchor.BN(1e9),
+                    minTokenOut: new anchor.BN(1e9),
+                })
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    metadata: metadataKeypair.toBase58(),
+                    curvePreset: null,
+                    creatorTokenAccount: spl.getAssociatedTokenAddressSync(
+                        mintSigner.publicKey,
+                        owner.publicKey
+                    ),
+                    platformTokenAccount: null,
+                })
+                .signers([owner, mintSigner])
+                .rpc();
+            assert.fail("Expected the initial buy to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "SlippageExceeded");
+        }
     });
 
     it("Cannot create the same token again", async () => {
@@ -50,9 +318,12 @@ describe("Solana pump fun", () => {
             await program.methods
                 .createToken(tokenDetails)
                 .accounts({
+                    mint: keypairs.mintKeypair,
                     metadata: keypairs.metadataKeypair.toBase58(),
+                    curvePreset: null,
+                    platformTokenAccount: null,
                 })
-                .signers([owner])
+                .signers([owner, keypairs.mintSigner])
                 .rpc();
         } catch (err) {
             assert((err as Error).message);
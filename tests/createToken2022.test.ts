@@ -0,0 +1,128 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { program, platformParams, tokenDetails, deriveTokenKeypairs } from "./utils/constants";
+import * as spl from "@solana/spl-token";
+
+describe("Solana pump fun", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const owner = (provider.wallet as anchor.Wallet).payer;
+
+    const mintSigner = anchor.web3.Keypair.generate();
+    const { tokenInfoKeypair, escrowTokenAccountKeypair } = deriveTokenKeypairs(
+        mintSigner.publicKey
+    );
+
+    before(async () => {
+        await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
+    });
+
+    it("Can create a Token-2022 token with metadata on the mint", async () => {
+        await program.methods
+            .createToken2022(tokenDetails)
+            .accounts({
+                mint: mintSigner.publicKey,
+                curvePreset: null,
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+
+        const tokenInfo = await program.account.tokenInfo.fetch(tokenInfoKeypair.toBase58());
+        const metadata = await spl.getTokenMetadata(
+            provider.connection,
+            mintSigner.publicKey,
+            undefined,
+            spl.TOKEN_2022_PROGRAM_ID
+        );
+        const vaultBalance = await provider.connection.getTokenAccountBalance(
+            escrowTokenAccountKeypair
+        );
+
+        assert.equal(tokenInfo.token.toString(), mintSigner.publicKey.toString());
+        assert.equal(metadata.name, tokenDetails.name);
+        assert.equal(metadata.symbol, tokenDetails.symbol);
+        assert.equal(metadata.uri, tokenDetails.uri);
+        assert.equal(vaultBalance.value.amount, platformParams.totalSupply.toString());
+
+        const mint = await spl.getMint(
+            provider.connection,
+            mintSigner.publicKey,
+            undefined,
+            spl.TOKEN_2022_PROGRAM_ID
+        );
+        assert.isNull(mint.mintAuthority);
+        assert.isNull(mint.freezeAuthority);
+    });
+
+    it("Creator can update the metadata stored on the mint", async () => {
+        const newMetadata = {
+            name: "A much longer token name",
+            symbol: "LONGER",
+            uri: "https://www.example.com/a/much/longer/uri",
+        };
+
+        await program.methods
+            .updateTokenMetadata(newMetadata)
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: null,
+                tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+                tokenMetadataProgram: null,
+            })
+            .signers([owner])
+            .rpc();
+
+        const metadata = await spl.getTokenMetadata(
+            provider.connection,
+            mintSigner.publicKey,
+            undefined,
+            spl.TOKEN_2022_PROGRAM_ID
+        );
+
+        assert.equal(metadata.name, newMetadata.name);
+        assert.equal(metadata.symbol, newMetadata.symbol);
+        assert.equal(metadata.uri, newMetadata.uri);
+    });
+
+    it("Can buy and sell a Token-2022 token on the curve", async () => {
+        const buyerTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            mintSigner.publicKey,
+            owner.publicKey,
+            undefined,
+            spl.TOKEN_2022_PROGRAM_ID
+        );
+
+        await program.methods
+            .buyTokens(new anchor.BN(1e9))
+            .accounts({
+                mint: mintSigner.publicKey.toBase58(),
+                tokenInfo: tokenInfoKeypair.toBase58(),
+                userTokenAccount: buyerTokenAccount,
+                tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+                platformTokenAccount: null,
+            })
+            .signers([owner])
+            .rpc();
+
+        const boughtBalance = await provider.connection.getTokenAccountBalance(buyerTokenAccount);
+        assert(+boughtBalance.value.amount > 0);
+
+        await program.methods
+            .sellTokens(new anchor.BN(boughtBalance.value.amount))
+            .accounts({
+                mint: mintSigner.publicKey.toBase58(),
+                tokenInfo: tokenInfoKeypair.toBase58(),
+                tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+                platformTokenAccount: null,
+            })
+            .signers([owner])
+            .rpc();
+
+        const soldBalance = await provider.connection.getTokenAccountBalance(buyerTokenAccount);
+        assert.equal(soldBalance.value.amount, "0");
+    });
+});
@@ -0,0 +1,99 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { program, platformParams, tokenDetails, keypairs, seedStrings } from "./utils/constants";
+
+describe("Solana pump fun", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const owner = (provider.wallet as anchor.Wallet).payer;
+
+    const presetName = "micro";
+    const presetConfig = {
+        curveType: { exponential: {} },
+        totalSupply: new anchor.BN(10e9),
+        virtualSol: new anchor.BN(5e9),
+        virtualTokenReserve: new anchor.BN(12e9),
+        realTokenReserve: new anchor.BN(8e9),
+        targetPoolBalance: new anchor.BN(20e9),
+    };
+    const curvePreset = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from(seedStrings.curvePresetSeedString), Buffer.from(presetName)],
+        program.programId
+    )[0];
+
+    before(async () => {
+        await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
+    });
+
+    it("Cannot create a curve preset tokens could not launch with", async () => {
+        for (const [config, expectedError] of [
+            [{ ...presetConfig, virtualSol: new anchor.BN(0) }, "ZeroCurveParam"],
+            [{ ...presetConfig, totalSupply: new anchor.BN(0) }, "ZeroCurveParam"],
+            [
+                { ...presetConfig, targetPoolBalance: presetConfig.virtualSol },
+                "CurveParamOutOfBounds",
+            ],
+            [{ ...presetConfig, realTokenReserve: new anchor.BN(13e9) }, "InvalidTokenReserves"],
+        ] as const) {
+            try {
+                await program.methods
+                    .createCurvePreset({ name: presetName, config })
+                    .accounts({})
+                    .signers([owner])
+                    .rpc();
+                assert.fail("Expected the preset to be rejected");
+            } catch (err) {
+                assert.include((err as Error).message, expectedError);
+            }
+        }
+    });
+
+    it("Can create a curve preset", async () => {
+        await program.methods
+            .createCurvePreset({ name: presetName, config: presetConfig })
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const preset = await program.account.curvePreset.fetch(curvePreset);
+
+        assert.equal(preset.name, presetName);
+        assert.deepEqual(preset.config.curveType, presetConfig.curveType);
+        assert.equal(preset.config.totalSupply.toNumber(), presetConfig.totalSupply.toNumber());
+    });
+
+    it("Can create a token from a curve preset", async () => {
+        await program.methods
+            .createToken(tokenDetails)
+            .accounts({
+                mint: keypairs.mintKeypair,
+                metadata: keypairs.metadataKeypair.toBase58(),
+                curvePreset,
+                platformTokenAccount: null,
+            })
+            .signers([owner, keypairs.mintSigner])
+            .rpc();
+
+        const tokenInfo = await program.account.tokenInfo.fetch(
+            keypairs.tokenInfoKeypair.toBase58()
+        );
+
+        assert.equal(tokenInfo.curvePreset.toString(), curvePreset.toString());
+        assert.deepEqual(tokenInfo.curveType, presetConfig.curveType);
+        assert.equal(tokenInfo.totalSupply.toNumber(), presetConfig.totalSupply.toNumber());
+        assert.equal(tokenInfo.virtualSol.toNumber(), presetConfig.virtualSol.toNumber());
+        assert.equal(
+            tokenInfo.realTokenReserve.toNumber(),
+            presetConfig.realTokenReserve.toNumber()
+        );
+    });
+
+    it("Can close a curve preset", async () => {
+        await program.methods.closeCurvePreset().accounts({ curvePreset }).signers([owner]).rpc();
+
+        const preset = await provider.connection.getAccountInfo(curvePreset);
+
+        assert.isNull(preset);
+    });
+});
@@ -1,46 +1,67 @@
 import * as anchor from "@coral-xyz/anchor";
 import { assert } from "chai";
-import { program, keypairs } from "./utils/constants";
+import { program, platformParams, keypairs } from "./utils/constants";
 
 describe("Solana pump fun", () => {
     const provider = anchor.AnchorProvider.env();
     anchor.setProvider(provider);
 
     const owner = (provider.wallet as anchor.Wallet).payer;
-    const feeInBps = new anchor.BN(100); // 1%
-    const totalSupply = new anchor.BN(100e9);
-    const virtualSol = new anchor.BN(100e9);
-    const targetPoolBalance = new anchor.BN(150e9);
 
     it("Is initialized!", async () => {
-        const platformParams = {
-            owner: owner.publicKey,
-            feeInBps,
-            totalSupply,
-            virtualSol,
-            targetPoolBalance,
-        };
-
         await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
 
         const account = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
 
         assert.equal(account.owner.toString(), owner.publicKey.toString());
-        assert.equal(account.feeInBps.toNumber(), feeInBps.toNumber());
-        assert.equal(account.totalSupply.toNumber(), totalSupply.toNumber());
-        assert.equal(account.virtualSol.toNumber(), virtualSol.toNumber());
-        assert.equal(account.targetPoolBalance.toNumber(), targetPoolBalance.toNumber());
+        assert.equal(account.buyFeeBps.toNumber(), platformParams.buyFeeBps.toNumber());
+        assert.equal(account.sellFeeBps.toNumber(), platformParams.sellFeeBps.toNumber());
+        assert.equal(account.totalSupply.toNumber(), platformParams.totalSupply.toNumber());
+        assert.equal(account.virtualSol.toNumber(), platformParams.virtualSol.toNumber());
+        assert.equal(
+            account.virtualTokenReserve.toNumber(),
+            platformParams.virtualTokenReserve.toNumber()
+        );
+        assert.equal(
+            account.realTokenReserve.toNumber(),
+            platformParams.realTokenReserve.toNumber()
+        );
+        assert.equal(
+            account.targetPoolBalance.toNumber(),
+            platformParams.targetPoolBalance.toNumber()
+        );
         assert.equal(account.accumulatedFees.toNumber(), 0);
+        assert.deepEqual(account.curveType, platformParams.curveType);
+        assert.equal(
+            account.curveBounds.maxTotalSupply.toNumber(),
+            platformParams.curveBounds.maxTotalSupply.toNumber()
+        );
     });
 
-    it("Can change fees", async () => {
-        const newFeesInBps = new anchor.BN(200); // 2%
+    it("Can change buy and sell fees separately", async () => {
+        const newBuyFeeBps = new anchor.BN(50); // 0.5%
+        const newSellFeeBps = new anchor.BN(300); // 3%
 
-        await program.methods.changeFees(newFeesInBps).accounts({}).signers([owner]).rpc();
+        await program.methods.changeBuyFee(newBuyFeeBps).accounts({}).signers([owner]).rpc();
+        await program.methods.changeSellFee(newSellFeeBps).accounts({}).signers([owner]).rpc();
 
         const account = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
 
-        assert.equal(account.feeInBps.toNumber(), newFeesInBps.toNumber());
+        assert.equal(account.buyFeeBps.toNumber(), newBuyFeeBps.toNumber());
+        assert.equal(account.sellFeeBps.toNumber(), newSellFeeBps.toNumber());
+    });
+
+    it("Cannot set a fee above the maximum", async () => {
+        try {
+            await program.methods
+                .changeSellFee(new anchor.BN(501))
+                .accounts({})
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the fee to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "ExcessiveFees");
+        }
     });
 
     it("Can change total supply", async () => {
@@ -67,6 +88,35 @@ describe("Solana pump fun", () => {
         assert.equal(account.virtualSol.toNumber(), newVirtualSol.toNumber());
     });
 
+    it("Can change token reserves", async () => {
+        const newVirtualTokenReserve = new anchor.BN(150e9);
+        const newRealTokenReserve = new anchor.BN(120e9);
+
+        await program.methods
+            .changeTokenReserves(newVirtualTokenReserve, newRealTokenReserve)
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const account = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
+
+        assert.equal(account.virtualTokenReserve.toNumber(), newVirtualTokenReserve.toNumber());
+        assert.equal(account.realTokenReserve.toNumber(), newRealTokenReserve.toNumber());
+    });
+
+    it("Cannot set a real token reserve above the virtual one", async () => {
+        try {
+            await program.methods
+                .changeTokenReserves(new anchor.BN(100e9), new anchor.BN(110e9))
+                .accounts({})
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the reserves to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "InvalidTokenReserves");
+        }
+    });
+
     it("Can change target pool balance", async () => {
         const newTargetPoolBalance = new anchor.BN(200e9); // 2%
 
@@ -81,6 +131,67 @@ describe("Solana pump fun", () => {
         assert.equal(account.targetPoolBalance.toNumber(), newTargetPoolBalance.toNumber());
     });
 
+    it("Can change curve type", async () => {
+        const newCurveType = { linear: {} };
+
+        await program.methods.changeCurveType(newCurveType).accounts({}).signers([owner]).rpc();
+
+        const account = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
+
+        assert.deepEqual(account.curveType, newCurveType);
+    });
+
+    it("Can change curve bounds", async () => {
+        const newCurveBounds = {
+            ...platformParams.curveBounds,
+            minTotalSupply: new anchor.BN(10e9),
+            maxTotalSupply: new anchor.BN(1000e9),
+        };
+
+        await program.methods
+            .changeCurveBounds(newCurveBounds)
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const account = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
+
+        assert.equal(account.curveBounds.minTotalSupply.toNumber(), 10e9);
+        assert.equal(account.curveBounds.maxTotalSupply.toNumber(), 1000e9);
+    });
+
+    it("Can change min trade amounts", async () => {
+        const newMinBuySolAmount = new anchor.BN(5e6);
+        const newMinSellTokenAmount = new anchor.BN(5e6);
+
+        await program.methods
+            .changeMinTradeAmounts(newMinBuySolAmount, newMinSellTokenAmount)
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const account = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
+
+        assert.equal(account.minBuySolAmount.toNumber(), newMinBuySolAmount.toNumber());
+        assert.equal(account.minSellTokenAmount.toNumber(), newMinSellTokenAmount.toNumber());
+    });
+
+    it("Can change metadata policy", async () => {
+        const newMetadataPolicy = { immutable: {} };
+        const newMetadataUpdateWindow = new anchor.BN(0);
+
+        await program.methods
+            .changeMetadataPolicy(newMetadataPolicy, newMetadataUpdateWindow)
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const account = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
+
+        assert.deepEqual(account.metadataPolicy, newMetadataPolicy);
+        assert.equal(account.metadataUpdateWindow.toNumber(), 0);
+    });
+
     it("Can change owner", async () => {
         const newOwner = anchor.web3.Keypair.generate();
 
@@ -0,0 +1,101 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { program, platformParams, tokenDetails, keypairs, seedStrings } from "./utils/constants";
+import * as spl from "@solana/spl-token";
+
+describe("Solana pump fun", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const owner = (provider.wallet as anchor.Wallet).payer;
+
+    before(async () => {
+        await program.methods
+            .initialize({
+                ...platformParams,
+                launchWindow: {
+                    duration: new anchor.BN(3600),
+                    maxSolPerWallet: new anchor.BN(2e9),
+                    startFeeInBps: new anchor.BN(1000), // 10%
+                },
+            })
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        await program.methods
+            .createToken(tokenDetails)
+            .accounts({
+                mint: keypairs.mintKeypair,
+                metadata: keypairs.metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
+            })
+            .signers([owner, keypairs.mintSigner])
+            .rpc();
+    });
+
+    it("Charges the launch fee and tracks wallet spend during the launch window", async () => {
+        const solAmount = new anchor.BN(1.5e9);
+
+        const buyerTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            keypairs.mintKeypair,
+            owner.publicKey
+        );
+
+        await program.methods
+            .buyTokens(solAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                userTokenAccount: buyerTokenAccount,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+                platformTokenAccount: null,
+            })
+            .signers([owner])
+            .rpc();
+
+        const platform = await program.account.platform.fetch(
+            keypairs.platformKeypair.toBase58()
+        );
+        const buyerRecordKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from(seedStrings.buyerRecordSeedString),
+                keypairs.mintKeypair.toBuffer(),
+                owner.publicKey.toBuffer(),
+            ],
+            program.programId
+        )[0];
+        const buyerRecord = await program.account.buyerRecord.fetch(buyerRecordKeypair);
+
+        // Well above the 1% platform fee right after creation
+        assert(platform.accumulatedFees.toNumber() > 1e8);
+        assert.equal(buyerRecord.launchSolSpent.toString(), solAmount.toString());
+    });
+
+    it("Cannot buy past the wallet cap during the launch window", async () => {
+        const buyerTokenAccount = spl.getAssociatedTokenAddressSync(
+            keypairs.mintKeypair,
+            owner.publicKey
+        );
+
+        try {
+            await program.methods
+                .buyTokens(new anchor.BN(1e9))
+                .accounts({
+                    mint: keypairs.mintKeypair.toBase58(),
+                    tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                    userTokenAccount: buyerTokenAccount,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                    platformTokenAccount: null,
+                })
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the buy to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "LaunchWalletCapExceeded");
+        }
+    });
+});
@@ -0,0 +1,240 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+    program,
+    platformParams,
+    tokenDetails,
+    keypairs,
+    seedStrings,
+    deriveTokenKeypairs,
+} from "./utils/constants";
+import * as spl from "@solana/spl-token";
+
+describe("Solana pump fun", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const owner = (provider.wallet as anchor.Wallet).payer;
+
+    let buyerTokenAccount: anchor.web3.PublicKey;
+
+    before(async () => {
+        await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
+
+        await program.methods
+            .createToken(tokenDetails)
+            .accounts({
+                mint: keypairs.mintKeypair,
+                metadata: keypairs.metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
+            })
+            .signers([owner, keypairs.mintSigner])
+            .rpc();
+
+        buyerTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            keypairs.mintKeypair,
+            owner.publicKey
+        );
+    });
+
+    it("Quotes a buy that matches the executed trade", async () => {
+        const solAmount = new anchor.BN(1e9 + 1e7);
+
+        const quote = await program.methods
+            .quoteBuy(solAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                trader: owner.publicKey,
+                traderVolume: null,
+                platformTokenAccount: null,
+            })
+            .view();
+
+        assert.equal(quote.feeAmount.toNumber(), 1e7);
+        assert.isAbove(quote.priceImpactBps.toNumber(), 0);
+
+        await program.methods
+            .buyTokens(solAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                userTokenAccount: buyerTokenAccount,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+                platformTokenAccount: null,
+            })
+            .signers([owner])
+            .rpc();
+
+        const balance = await provider.connection.getTokenAccountBalance(buyerTokenAccount);
+        const tokenInfo = await program.account.tokenInfo.fetch(
+            keypairs.tokenInfoKeypair.toBase58()
+        );
+
+        assert.equal(balance.value.amount, quote.tokenAmount.toString());
+        assert.equal(tokenInfo.solReserve.toNumber(), quote.solReserveAfter.toNumber());
+        assert.equal(tokenInfo.tokenReserve.toNumber(), quote.tokenReserveAfter.toNumber());
+    });
+
+    it("Quotes a sell", async () => {
+        const tokenAmount = new anchor.BN(5e8);
+        const tokenInfo = await program.account.tokenInfo.fetch(
+            keypairs.tokenInfoKeypair.toBase58()
+        );
+
+        const quote = await program.methods
+            .quoteSell(tokenAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                trader: owner.publicKey,
+                traderVolume: null,
+                platformTokenAccount: null,
+            })
+            .view();
+
+        assert.equal(
+            quote.solAmount.toNumber(),
+            quote.solAmountAfterFee.toNumber() + quote.feeAmount.toNumber()
+        );
+        assert.equal(
+            quote.realTokenReserveAfter.toNumber(),
+            tokenInfo.realTokenReserve.toNumber() + tokenAmount.toNumber()
+        );
+    });
+
+    it("Charges a higher fee on trades with a large price impact", async () => {
+        const solAmount = new anchor.BN(10e9);
+
+        const baseQuote = await program.methods
+            .quoteBuy(solAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                trader: owner.publicKey,
+                traderVolume: null,
+                platformTokenAccount: null,
+            })
+            .view();
+
+        assert.equal(baseQuote.feeInBps.toNumber(), platformParams.buyFeeBps.toNumber());
+
+        await program.methods
+            .changePriceImpactFee({
+                thresholdBps: new anchor.BN(100),
+                slopeBps: new anchor.BN(1000),
+            })
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const quote = await program.methods
+            .quoteBuy(solAmount)
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                trader: owner.publicKey,
+                traderVolume: null,
+                platformTokenAccount: null,
+            })
+            .view();
+
+        assert.isAbove(quote.feeInBps.toNumber(), platformParams.buyFeeBps.toNumber());
+        assert.isAbove(quote.feeAmount.toNumber(), baseQuote.feeAmount.toNumber());
+        assert.isBelow(quote.tokenAmount.toNumber(), baseQuote.tokenAmount.toNumber());
+
+        await program.methods
+            .changePriceImpactFee({ thresholdBps: new anchor.BN(0), slopeBps: new anchor.BN(0) })
+            .accounts({})
+            .signers([owner])
+            .rpc();
+    });
+
+    it("Discounts the fee of traders in a volume tier", async () => {
+        const traderVolumeKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from(seedStrings.traderVolumeSeedString), owner.publicKey.toBuffer()],
+            program.programId
+        )[0];
+        const traderVolume = await program.account.traderVolume.fetch(traderVolumeKeypair);
+        assert.isAbove(traderVolume.volumeSol.toNumber(), 0);
+
+        await program.methods
+            .changeFeeTiers([
+                { minVolumeSol: new anchor.BN(1e9), discountBps: new anchor.BN(5000) },
+            ])
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const quote = await program.methods
+            .quoteBuy(new anchor.BN(1e8))
+            .accounts({
+                mint: keypairs.mintKeypair.toBase58(),
+                tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                trader: owner.publicKey,
+                traderVolume: traderVolumeKeypair,
+                platformTokenAccount: null,
+            })
+            .view();
+
+        assert.equal(quote.feeInBps.toNumber(), platformParams.buyFeeBps.toNumber() / 2);
+
+        await program.methods.changeFeeTiers([]).accounts({}).signers([owner]).rpc();
+    });
+
+    it("Cannot quote a buy past the end of the curve", async () => {
+        // A linear curve whose whole supply is for sale and sells out for 15 SOL
+        const presetName = "sell-out";
+        const curvePreset = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from(seedStrings.curvePresetSeedString), Buffer.from(presetName)],
+            program.programId
+        )[0];
+        await program.methods
+            .createCurvePreset({
+                name: presetName,
+                config: {
+                    curveType: { linear: {} },
+                    totalSupply: new anchor.BN(100e9),
+                    virtualSol: new anchor.BN(10e9),
+                    virtualTokenReserve: new anchor.BN(100e9),
+                    realTokenReserve: new anchor.BN(100e9),
+                    targetPoolBalance: new anchor.BN(100e9),
+                },
+            })
+            .accounts({})
+            .signers([owner])
+            .rpc();
+
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { metadataKeypair, tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+        await program.methods
+            .createToken(tokenDetails)
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: metadataKeypair.toBase58(),
+                curvePreset,
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+
+        try {
+            await program.methods
+                .quoteBuy(new anchor.BN(20e9))
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    tokenInfo: tokenInfoKeypair,
+                    trader: owner.publicKey,
+                    traderVolume: null,
+                    platformTokenAccount: null,
+                })
+                .view();
+            assert.fail("Expected the quote to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "RealTokenReserveExceeded");
+        }
+    });
+});
@@ -1,6 +1,13 @@
 import * as anchor from "@coral-xyz/anchor";
 import { assert } from "chai";
-import { program, tokenDetails, keypairs, seedStrings } from "./utils/constants";
+import {
+    program,
+    platformParams,
+    tokenDetails,
+    keypairs,
+    seedStrings,
+    deriveTokenKeypairs,
+} from "./utils/constants";
 import * as spl from "@solana/spl-token";
 
 describe("Solana pump fun", () => {
@@ -8,29 +15,21 @@ describe("Solana pump fun", () => {
     anchor.setProvider(provider);
 
     const owner = (provider.wallet as anchor.Wallet).payer;
-    const feeInBps = new anchor.BN(100); // 1%
-    const totalSupply = new anchor.BN(100e9);
-    const virtualSol = new anchor.BN(100e9);
-    const targetPoolBalance = new anchor.BN(150e9);
+
     let buyerTokenAccount: anchor.web3.PublicKey;
 
     before(async () => {
-        const platformParams = {
-            owner: owner.publicKey,
-            feeInBps,
-            totalSupply,
-            virtualSol,
-            targetPoolBalance,
-        };
-
         await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
 
         await program.methods
             .createToken(tokenDetails)
             .accounts({
+                mint: keypairs.mintKeypair,
                 metadata: keypairs.metadataKeypair.toBase58(),
+                curvePreset: null,
+                platformTokenAccount: null,
             })
-            .signers([owner])
+            .signers([owner, keypairs.mintSigner])
             .rpc();
 
         const solAmount = new anchor.BN(1e9 + 1e7);
@@ -48,6 +47,8 @@ describe("Solana pump fun", () => {
                 mint: keypairs.mintKeypair.toBase58(),
                 tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
                 userTokenAccount: buyerTokenAccount,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+                platformTokenAccount: null,
             })
             .signers([owner])
             .rpc();
@@ -55,15 +56,135 @@ describe("Solana pump fun", () => {
 
     it("Can sell tokens", async () => {
         const tokenAmount = new anchor.BN(5e8);
-        const name = tokenDetails.name;
 
         await program.methods
-            .sellTokens(tokenAmount, name)
+            .sellTokens(tokenAmount)
             .accounts({
                 mint: keypairs.mintKeypair.toBase58(),
                 tokenInfo: keypairs.tokenInfoKeypair.toBase58(),
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+                platformTokenAccount: null,
             })
             .signers([owner])
             .rpc();
+
+        const tokenInfo = await program.account.tokenInfo.fetch(
+            keypairs.tokenInfoKeypair.toBase58()
+        );
+        const vaultBalance = await provider.connection.getTokenAccountBalance(
+            keypairs.escrowTokenAccountKeypair
+        );
+
+        assert.equal(
+            vaultBalance.value.amount,
+            tokenInfo.realTokenReserve.add(tokenInfo.poolTokenReserve).toString()
+        );
+    });
+
+    it("Tracks trading statistics per token and platform-wide", async () => {
+        const tokenInfo = await program.account.tokenInfo.fetch(
+            keypairs.tokenInfoKeypair.toBase58()
+        );
+        const platform = await program.account.platform.fetch(keypairs.platformKeypair.toBase58());
+        const platformStats = await program.account.platformStats.fetch(
+            keypairs.platformStatsKeypair.toBase58()
+        );
+
+        assert.equal(tokenInfo.tradeStats.tradeCount.toNumber(), 2);
+        assert.equal(tokenInfo.tradeStats.uniqueBuyers.toNumber(), 1);
+        assert.equal(tokenInfo.tradeStats.buyVolumeSol.toNumber(), 1e9 + 1e7);
+        assert.equal(tokenInfo.tradeStats.sellVolumeTokens.toNumber(), 5e8);
+        assert.equal(
+            tokenInfo.tradeStats.totalFees.toNumber(),
+            platform.accumulatedFees.toNumber()
+        );
+        assert.equal(platformStats.tokensCreated.toNumber(), 1);
+        assert.equal(platformStats.tradeStats.tradeCount.toNumber(), 2);
+    });
+
+    it("Tracks the trader's position and lets them close it", async () => {
+        const positionKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from(seedStrings.positionSeedString),
+                keypairs.mintKeypair.toBuffer(),
+                owner.publicKey.toBuffer(),
+            ],
+            program.programId
+        )[0];
+        const position = await program.account.position.fetch(positionKeypair);
+        const balance = await provider.connection.getTokenAccountBalance(buyerTokenAccount);
+
+        assert.equal(position.tokensHeld.toString(), balance.value.amount);
+        assert.equal(position.totalSolIn.toNumber(), 1e9 + 1e7);
+        assert.isAbove(position.totalSolOut.toNumber(), 0);
+        assert.isAbove(position.avgEntryPrice.toNumber(), 0);
+
+        await program.methods
+            .closePosition()
+            .accounts({ mint: keypairs.mintKeypair })
+            .signers([owner])
+            .rpc();
+
+        assert.isNull(await provider.connection.getAccountInfo(positionKeypair));
+    });
+
+    it("Can trade tokens on the linear and exponential curves", async () => {
+        // 1 SOL (after the 1% fee) on a curve starting at 100 SOL of virtual sol for 100e9 tokens
+        for (const [curveType, expectedTokens] of [
+            [{ linear: {} }, 995_049_383], // T * (sqrt(1 + 2C / V) - 1)
+            [{ exponential: {} }, 995_033_085], // T * ln(1 + C / V)
+        ] as const) {
+            const mintSigner = anchor.web3.Keypair.generate();
+            const { metadataKeypair, tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+            await program.methods
+                .createToken({ ...tokenDetails, curveType })
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    metadata: metadataKeypair.toBase58(),
+                    curvePreset: null,
+                    platformTokenAccount: null,
+                })
+                .signers([owner, mintSigner])
+                .rpc();
+
+            const userTokenAccount = await spl.createAssociatedTokenAccount(
+                provider.connection,
+                owner,
+                mintSigner.publicKey,
+                owner.publicKey
+            );
+            await program.methods
+                .buyTokens(new anchor.BN(1e9 + 1e7))
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    tokenInfo: tokenInfoKeypair,
+                    userTokenAccount,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                    platformTokenAccount: null,
+                })
+                .signers([owner])
+                .rpc();
+
+            const balance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+            assert.approximately(+balance.value.amount, expectedTokens, 1);
+
+            const solBefore = await provider.connection.getBalance(owner.publicKey);
+            await program.methods
+                .sellTokens(new anchor.BN(balance.value.amount))
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    tokenInfo: tokenInfoKeypair,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                    platformTokenAccount: null,
+                })
+                .signers([owner])
+                .rpc();
+            const solAfter = await provider.connection.getBalance(owner.publicKey);
+
+            // Selling straight back returns the sol minus both fees, never more than was paid
+            assert.isAbove(solAfter - solBefore, 9.7e8);
+            assert.isBelow(solAfter - solBefore, 1e9 + 1e7);
+        }
     });
 });
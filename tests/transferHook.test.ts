@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { program, platformParams, tokenDetails, deriveTokenKeypairs } from "./utils/constants";
+import { TransferHookStub } from "../target/types/transfer_hook_stub";
+import * as spl from "@solana/spl-token";
+
+describe("Solana pump fun", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const hookProgram = anchor.workspace.TransferHookStub as Program<TransferHookStub>;
+
+    const owner = (provider.wallet as anchor.Wallet).payer;
+
+    const mintSigner = anchor.web3.Keypair.generate();
+    const { tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+    const extraAccountMetaListKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("extra-account-metas"), mintSigner.publicKey.toBuffer()],
+        hookProgram.programId
+    )[0];
+    const transferCounterKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("transfer_counter"), mintSigner.publicKey.toBuffer()],
+        hookProgram.programId
+    )[0];
+    const hookAccounts = [
+        { pubkey: hookProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: extraAccountMetaListKeypair, isSigner: false, isWritable: false },
+        { pubkey: transferCounterKeypair, isSigner: false, isWritable: true },
+    ];
+
+    before(async () => {
+        await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
+
+        await program.methods
+            .createToken2022WithHook(tokenDetails)
+            .accounts({
+                mint: mintSigner.publicKey,
+                transferHookProgram: hookProgram.programId,
+                curvePreset: null,
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+
+        await hookProgram.methods
+            .initializeExtraAccountMetaList()
+            .accounts({ mint: mintSigner.publicKey })
+            .signers([owner])
+            .rpc();
+    });
+
+    it("Can buy and sell a token with a transfer hook", async () => {
+        const buyerTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            mintSigner.publicKey,
+            owner.publicKey,
+            undefined,
+            spl.TOKEN_2022_PROGRAM_ID
+        );
+
+        await program.methods
+            .buyTokens(new anchor.BN(1e9))
+            .accounts({
+                mint: mintSigner.publicKey.toBase58(),
+                tokenInfo: tokenInfoKeypair.toBase58(),
+                userTokenAccount: buyerTokenAccount,
+                tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+                platformTokenAccount: null,
+            })
+            .remainingAccounts(hookAccounts)
+            .signers([owner])
+            .rpc();
+
+        const boughtBalance = await provider.connection.getTokenAccountBalance(buyerTokenAccount);
+
+        await program.methods
+            .sellTokens(new anchor.BN(boughtBalance.value.amount))
+            .accounts({
+                mint: mintSigner.publicKey.toBase58(),
+                tokenInfo: tokenInfoKeypair.toBase58(),
+                tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+                platformTokenAccount: null,
+            })
+            .remainingAccounts(hookAccounts)
+            .signers([owner])
+            .rpc();
+
+        const transferCounter = await hookProgram.account.transferCounter.fetch(
+            transferCounterKeypair
+        );
+
+        assert.equal(transferCounter.transfers.toNumber(), 2);
+    });
+
+    it("Cannot trade a hooked token without the hook accounts", async () => {
+        const buyerTokenAccount = spl.getAssociatedTokenAddressSync(
+            mintSigner.publicKey,
+            owner.publicKey,
+            false,
+            spl.TOKEN_2022_PROGRAM_ID
+        );
+
+        try {
+            await program.methods
+                .buyTokens(new anchor.BN(1e9))
+                .accounts({
+                    mint: mintSigner.publicKey.toBase58(),
+                    tokenInfo: tokenInfoKeypair.toBase58(),
+                    userTokenAccount: buyerTokenAccount,
+                    tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+                    platformTokenAccount: null,
+                })
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the transfer to fail without the hook accounts");
+        } catch (err) {
+            // TransferHookError::IncorrectAccount, raised when the hook program or its validation
+            // account is missing from the accounts forwarded to the transfer
+            assert.include((err as Error).message, "custom program error: 0x7dc8348c");
+        }
+    });
+});
@@ -6,16 +6,63 @@ const program = anchor.workspace.SolanaPumpFun as Program<SolanaPumpFun>;
 
 const seedStrings = {
     platformSeedString: "platform",
-    mintSeedString: "mint",
     tokenInfoSeedString: "token",
     tokenAccountSeedString: "token_account",
+    curvePresetSeedString: "curve_preset",
+    vestingSeedString: "vesting",
+    buyerRecordSeedString: "buyer_record",
+    traderVolumeSeedString: "trader_volume",
+    platformStatsSeedString: "platform_stats",
+    positionSeedString: "position",
     metadataSeedString: "metadata",
 };
 
+const platformParams = {
+    owner: anchor.AnchorProvider.env().wallet.publicKey,
+    buyFeeBps: new anchor.BN(100), // 1%
+    sellFeeBps: new anchor.BN(100), // 1%
+    totalSupply: new anchor.BN(100e9),
+    virtualSol: new anchor.BN(100e9),
+    virtualTokenReserve: new anchor.BN(100e9),
+    realTokenReserve: new anchor.BN(80e9),
+    targetPoolBalance: new anchor.BN(150e9),
+    curveType: { constantProduct: {} },
+    curveBounds: {
+        minTotalSupply: new anchor.BN(50e9),
+        maxTotalSupply: new anchor.BN(200e9),
+        minVirtualSol: new anchor.BN(50e9),
+        maxVirtualSol: new anchor.BN(200e9),
+        minTargetPoolBalance: new anchor.BN(100e9),
+        maxTargetPoolBalance: new anchor.BN(300e9),
+    },
+    minBuySolAmount: new anchor.BN(1e6),
+    minSellTokenAmount: new anchor.BN(1e6),
+    metadataPolicy: { creatorUpdatable: {} },
+    metadataUpdateWindow: new anchor.BN(3600),
+    launchWindow: {
+        duration: new anchor.BN(0),
+        maxSolPerWallet: new anchor.BN(0),
+        startFeeInBps: new anchor.BN(0),
+    },
+    maxHoldingBps: new anchor.BN(0),
+    priceImpactFee: { thresholdBps: new anchor.BN(0), slopeBps: new anchor.BN(0) },
+    feeTiers: [],
+    platformTokenMint: null,
+    holderDiscountTiers: [],
+};
+
 const tokenDetails = {
     name: "Token",
     symbol: "T",
-    uri: "www.example.com",
+    uri: "https://www.example.com",
+    curveType: null,
+    totalSupply: null,
+    virtualSol: null,
+    targetPoolBalance: null,
+    initialBuySol: null,
+    minTokenOut: new anchor.BN(0),
+    creatorVesting: null,
+    maxHoldingBps: null,
 };
 
 const metadataTokenProgramPubkey = new anchor.web3.PublicKey(
@@ -26,33 +73,75 @@ const platformKeypair = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from(seedStrings.platformSeedString)],
     program.programId
 )[0];
-const mintKeypair = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from(seedStrings.mintSeedString), Buffer.from(tokenDetails.name)],
-    program.programId
-)[0];
-const metadataKeypair = anchor.web3.PublicKey.findProgramAddressSync(
-    [
-        Buffer.from(seedStrings.metadataSeedString),
-        metadataTokenProgramPubkey.toBuffer(),
-        mintKeypair.toBuffer(),
-    ],
-    metadataTokenProgramPubkey
-)[0];
-const tokenInfoKeypair = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from(seedStrings.tokenInfoSeedString), Buffer.from(tokenDetails.name)],
-    program.programId
-)[0];
-const escrowTokenAccountKeypair = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from(seedStrings.tokenAccountSeedString), mintKeypair.toBuffer()],
+const platformStatsKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(seedStrings.platformStatsSeedString)],
     program.programId
 )[0];
+const mintSigner = anchor.web3.Keypair.generate();
+const mintKeypair = mintSigner.publicKey;
+const deriveTokenKeypairs = (mint: anchor.web3.PublicKey) => {
+    const metadataKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+            Buffer.from(seedStrings.metadataSeedString),
+            metadataTokenProgramPubkey.toBuffer(),
+            mint.toBuffer(),
+        ],
+        metadataTokenProgramPubkey
+    )[0];
+    const tokenInfoKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from(seedStrings.tokenInfoSeedString), mint.toBuffer()],
+        program.programId
+    )[0];
+    const escrowTokenAccountKeypair = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from(seedStrings.tokenAccountSeedString), mint.toBuffer()],
+        program.programId
+    )[0];
+
+    return { metadataKeypair, tokenInfoKeypair, escrowTokenAccountKeypair };
+};
+
+// Reads the fields the tests check from a Metaplex metadata account
+const fetchMetaplexMetadata = async (metadata: anchor.web3.PublicKey) => {
+    const { data } = await program.provider.connection.getAccountInfo(metadata);
+    let offset = 1 + 32 + 32; // key, update authority and mint
+    const readString = () => {
+        const length = data.readUInt32LE(offset);
+        const value = data.subarray(offset + 4, offset + 4 + length).toString().replace(/\0/g, "");
+        offset += 4 + length;
+        return value;
+    };
+    const name = readString();
+    const symbol = readString();
+    const uri = readString();
+    offset += 2; // seller fee basis points
+    if (data[offset++] === 1) {
+        offset += 4 + data.readUInt32LE(offset) * 34; // creators
+    }
+    offset += 1; // primary sale happened
+    const isMutable = data[offset] === 1;
+
+    return { name, symbol, uri, isMutable };
+};
+
+const { metadataKeypair, tokenInfoKeypair, escrowTokenAccountKeypair } =
+    deriveTokenKeypairs(mintKeypair);
 
 const keypairs = {
     platformKeypair,
+    platformStatsKeypair,
+    mintSigner,
     mintKeypair,
     metadataKeypair,
     tokenInfoKeypair,
     escrowTokenAccountKeypair,
 };
 
-export { program, tokenDetails, seedStrings, keypairs };
+export {
+    program,
+    platformParams,
+    tokenDetails,
+    seedStrings,
+    keypairs,
+    deriveTokenKeypairs,
+    fetchMetaplexMetadata,
+};
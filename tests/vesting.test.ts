@@ -0,0 +1,195 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+    program,
+    platformParams,
+    tokenDetails,
+    seedStrings,
+    deriveTokenKeypairs,
+} from "./utils/constants";
+import * as spl from "@solana/spl-token";
+
+describe("Solana pump fun", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const owner = (provider.wallet as anchor.Wallet).payer;
+
+    before(async () => {
+        await program.methods.initialize(platformParams).accounts({}).signers([owner]).rpc();
+    });
+
+    const deriveVestingKeypair = (mint: anchor.web3.PublicKey) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from(seedStrings.vestingSeedString), mint.toBuffer()],
+            program.programId
+        )[0];
+
+    const createVestedToken = async (
+        mintSigner: anchor.web3.Keypair,
+        cliffDuration: number,
+        vestingDuration: number
+    ) => {
+        const { metadataKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+        await program.methods
+            .createToken({
+                ...tokenDetails,
+                creatorVesting: {
+                    allocationBps: new anchor.BN(500), // 5%
+                    cliffDuration: new anchor.BN(cliffDuration),
+                    vestingDuration: new anchor.BN(vestingDuration),
+                },
+            })
+            .accounts({
+                mint: mintSigner.publicKey,
+                metadata: metadataKeypair.toBase58(),
+                curvePreset: null,
+                vesting: deriveVestingKeypair(mintSigner.publicKey),
+                platformTokenAccount: null,
+            })
+            .signers([owner, mintSigner])
+            .rpc();
+    };
+
+    // Fills the curve up to its target pool balance, fee included, so that the token launches
+    const launchToken = async (mint: anchor.web3.PublicKey) => {
+        const userTokenAccount = await spl.createAssociatedTokenAccount(
+            provider.connection,
+            owner,
+            mint,
+            owner.publicKey
+        );
+        const solAmount = platformParams.targetPoolBalance
+            .sub(platformParams.virtualSol)
+            .muln(10_000 + platformParams.buyFeeBps.toNumber())
+            .divn(10_000);
+
+        await program.methods
+            .buyTokens(solAmount)
+            .accounts({
+                mint,
+                tokenInfo: deriveTokenKeypairs(mint).tokenInfoKeypair,
+                userTokenAccount,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+                platformTokenAccount: null,
+            })
+            .signers([owner])
+            .rpc();
+
+        return userTokenAccount;
+    };
+
+    it("Sets the creator allocation aside from the curve", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { tokenInfoKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+        await createVestedToken(mintSigner, 0, 1);
+
+        const tokenInfo = await program.account.tokenInfo.fetch(tokenInfoKeypair.toBase58());
+        const vesting = await program.account.vesting.fetch(
+            deriveVestingKeypair(mintSigner.publicKey)
+        );
+        const allocation = platformParams.totalSupply.muln(500).divn(10_000);
+
+        assert.equal(vesting.totalAmount.toString(), allocation.toString());
+        assert.equal(tokenInfo.vestingTokenReserve.toString(), allocation.toString());
+        assert.equal(
+            tokenInfo.realTokenReserve.toString(),
+            platformParams.realTokenReserve.sub(allocation).toString()
+        );
+        assert.equal(
+            tokenInfo.tokenReserve.toString(),
+            platformParams.virtualTokenReserve.sub(allocation).toString()
+        );
+
+        // Let the one second vesting elapse before claiming everything
+        await new Promise((resolve) => setTimeout(resolve, 2000));
+
+        // Vested tokens stay locked while the token trades on the curve
+        try {
+            await program.methods
+                .claimVested()
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                })
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the claim to be rejected before launch");
+        } catch (err) {
+            assert.include((err as Error).message, "NotLaunched");
+        }
+
+        const creatorTokenAccount = await launchToken(mintSigner.publicKey);
+        const balanceBefore = await provider.connection.getTokenAccountBalance(creatorTokenAccount);
+
+        await program.methods
+            .claimVested()
+            .accounts({
+                mint: mintSigner.publicKey,
+                tokenProgram: spl.TOKEN_PROGRAM_ID,
+            })
+            .signers([owner])
+            .rpc();
+
+        const balanceAfter = await provider.connection.getTokenAccountBalance(creatorTokenAccount);
+
+        assert.equal(
+            new anchor.BN(balanceAfter.value.amount)
+                .sub(new anchor.BN(balanceBefore.value.amount))
+                .toString(),
+            allocation.toString()
+        );
+    });
+
+    it("Cannot claim before the cliff", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+
+        await createVestedToken(mintSigner, 3600, 7200);
+        await launchToken(mintSigner.publicKey);
+
+        try {
+            await program.methods
+                .claimVested()
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    tokenProgram: spl.TOKEN_PROGRAM_ID,
+                })
+                .signers([owner])
+                .rpc();
+            assert.fail("Expected the claim to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "NothingToClaim");
+        }
+    });
+
+    it("Cannot allocate more than the platform cap to the creator", async () => {
+        const mintSigner = anchor.web3.Keypair.generate();
+        const { metadataKeypair } = deriveTokenKeypairs(mintSigner.publicKey);
+
+        try {
+            await program.methods
+                .createToken({
+                    ...tokenDetails,
+                    creatorVesting: {
+                        allocationBps: new anchor.BN(2_000),
+                        cliffDuration: new anchor.BN(0),
+                        vestingDuration: new anchor.BN(3600),
+                    },
+                })
+                .accounts({
+                    mint: mintSigner.publicKey,
+                    metadata: metadataKeypair.toBase58(),
+                    curvePreset: null,
+                    vesting: deriveVestingKeypair(mintSigner.publicKey),
+                    platformTokenAccount: null,
+                })
+                .signers([owner, mintSigner])
+                .rpc();
+            assert.fail("Expected the allocation to be rejected");
+        } catch (err) {
+            assert.include((err as Error).message, "ExcessiveCreatorAllocation");
+        }
+    });
+});